@@ -1,23 +1,41 @@
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+
 type Int = u16;
 type InputType = Vec<Int>;
 
+pub const INVARIANTS: &[Invariant] = &[];
+
+pub fn parse(file: &str) -> InputType {
+    file.trim().lines().flat_map(|s| s.parse::<Int>()).collect()
+}
+
 fn read_input() -> InputType {
     let file = fs::read_to_string("./src/bin/day00/input.txt").unwrap();
 
-    file.trim().lines().flat_map(|s| s.parse::<Int>()).collect()
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
 }
 
-fn part1(input: InputType) -> Int {
+fn part1(_input: InputType) -> Int {
     0
 }
 
-fn part2(input: InputType) -> Int {
+fn part2(_input: InputType) -> Int {
     0
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -0,0 +1,133 @@
+use std::fmt;
+use std::str::FromStr;
+
+use num::BigInt;
+use tracing::info_span;
+
+/// The result of one part of a day, whatever type the solution computes it in.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    Big(BigInt),
+    Text(String),
+}
+
+macro_rules! from_int {
+    ($variant:ident, $wide:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $wide)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+from_int!(Signed, i128, i8, i16, i32, i64, i128, isize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Answer::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Big(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// Answers are equal when they would be submitted as the same text, whichever type computed them.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Signed(a), Answer::Signed(b)) => a == b,
+            (Answer::Unsigned(a), Answer::Unsigned(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+/// Reads back a displayed answer, picking the narrowest variant that holds it.
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(if let Ok(n) = s.parse() {
+            Answer::Unsigned(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::Signed(n)
+        } else if let Ok(n) = s.parse() {
+            Answer::Big(n)
+        } else {
+            Answer::Text(s.to_string())
+        })
+    }
+}
+
+/// How a day turns its parsed input into answers.
+pub enum Parts<I> {
+    /// Each part solves its own copy of the input.
+    Separate(fn(I) -> Answer, fn(I) -> Answer),
+    /// Both parts fall out of the same pass over the input.
+    Combined(fn(I) -> (Answer, Answer)),
+    /// Only one puzzle, as on day 25.
+    Single(fn(I) -> Answer),
+}
+
+impl<I> Parts<I> {
+    pub const fn count(&self) -> u8 {
+        match self {
+            Parts::Separate(_, _) | Parts::Combined(_) => 2,
+            Parts::Single(_) => 1,
+        }
+    }
+
+    pub const fn combined(&self) -> bool {
+        matches!(self, Parts::Combined(_))
+    }
+}
+
+impl<I: Clone> Parts<I> {
+    /// Parses the file and passes each answer to `report` as soon as it is known, so separate
+    /// parts can be timed individually. Parsing and each part run in a tracing span of their own.
+    pub fn run(&self, file: &str, parse: fn(&str) -> I, report: &mut dyn FnMut(Answer)) {
+        let input = info_span!("parse").in_scope(|| parse(file));
+
+        match self {
+            Parts::Separate(part1, part2) => {
+                report(info_span!("part1").in_scope(|| part1(input.clone())));
+                report(info_span!("part2").in_scope(|| part2(input)));
+            }
+            Parts::Combined(solve) => {
+                let (part1, part2) = info_span!("parts").in_scope(|| solve(input));
+
+                report(part1);
+                report(part2);
+            }
+            Parts::Single(part1) => report(info_span!("part1").in_scope(|| part1(input))),
+        }
+    }
+}
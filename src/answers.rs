@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::answer::Answer;
+
+pub const ANSWERS_PATH: &str = "./answers.txt";
+
+/// The answers the puzzles give for each day's `ex.txt`
+pub const EXAMPLE_ANSWERS_PATH: &str = "./example_answers.txt";
+
+/// Known-correct answers keyed by `(day, part)`, one `day part answer` per line.
+#[derive(Debug, Default, Clone)]
+pub struct Answers(BTreeMap<(u8, u8), Answer>);
+
+impl Answers {
+    pub fn parse(file: &str) -> Answers {
+        Answers(
+            file.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter_map(|line| {
+                    let mut fields = line.split_whitespace();
+
+                    let day = fields.next()?.parse().ok()?;
+                    let part = fields.next()?.parse().ok()?;
+                    let answer = fields.next()?.parse().ok()?;
+
+                    Some(((day, part), answer))
+                })
+                .collect(),
+        )
+    }
+
+    /// A missing file is treated as an empty registry.
+    pub fn load(path: impl AsRef<Path>) -> Answers {
+        fs::read_to_string(path)
+            .map(|file| Answers::parse(&file))
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&Answer> {
+        self.0.get(&(day, part))
+    }
+}
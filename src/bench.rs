@@ -0,0 +1,16 @@
+//! Times a day's faster code against what it replaced, on generated inputs large enough to tell
+//! them apart.
+
+use std::time::Duration;
+
+use crate::days::*;
+
+/// Parses the input and times each way of solving it, by name, or explains how they disagree
+pub type Bench = fn(&str) -> Result<Vec<(&'static str, Duration)>, String>;
+
+pub fn benchmark(day: u8) -> Option<Bench> {
+    Some(match day {
+        19 => day19::bench,
+        _ => return None,
+    })
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::process::exit;
+
+use advent_of_code_2023::bench::benchmark;
+use advent_of_code_2023::generators::generator;
+use advent_of_code_2023::invariants;
+use advent_of_code_2023::registry::solutions_for;
+use advent_of_code_2023::rng::Rng;
+
+const DEFAULT_SIZE: usize = 1_000_000;
+
+/// Times a day's faster code against what it replaced on a large generated input, or on the
+/// given file.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let seed = crate::take_option(&mut args, "--seed")
+        .map(|n| n.parse().expect("Seed is not a number"))
+        .unwrap_or(0);
+    let size = crate::take_option(&mut args, "--size")
+        .map(|n| n.parse().expect("Size is not a number"))
+        .unwrap_or(DEFAULT_SIZE);
+
+    let day: u8 = args
+        .first()
+        .expect("Day not found in arguments")
+        .parse()
+        .expect("Day is not a number");
+
+    let Some(bench) = benchmark(day) else {
+        eprintln!("Day {day} has no benchmark");
+        exit(1);
+    };
+
+    let input = match args.get(1) {
+        Some(path) => fs::read_to_string(path).expect("Cannot read input"),
+        None => generator(day).expect("Day has no generator")(&mut Rng::new(seed), size),
+    };
+
+    let solution = solutions_for(day).next().unwrap();
+
+    if let Err(message) = invariants::check(&input, solution.invariants) {
+        eprintln!("{message}");
+        exit(1);
+    }
+
+    match bench(&input) {
+        Ok(timings) => {
+            for (name, elapsed) in timings {
+                println!("{name:<16}{elapsed:>12.2?}");
+            }
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::fs;
+use std::process::exit;
+
+use advent_of_code_2023::invariants::{violations, warnings_for};
+use advent_of_code_2023::registry::SOLUTIONS;
+
+/// Checks each day's input, or the given file, against its solution's invariants, also pointing
+/// out what the solution copes with but looks like a mistake.
+pub fn run(args: &[String]) {
+    let day: Option<u8> = args
+        .first()
+        .map(|day| day.parse().expect("Day is not a number"));
+    let path = args.get(1);
+
+    let mut failed = false;
+
+    for solution in SOLUTIONS
+        .iter()
+        .filter(|s| day.is_none_or(|day| s.day == day))
+    {
+        let path = path.cloned().unwrap_or_else(|| solution.input_path());
+
+        let Ok(input) = fs::read_to_string(&path) else {
+            println!("{}: no input at {}", solution.bin, path);
+            continue;
+        };
+
+        let violations = violations(&input, solution.invariants);
+
+        if violations.is_empty() {
+            let warnings = warnings_for(solution.day)
+                .map(|warnings| warnings(&input))
+                .unwrap_or_default();
+
+            match warnings.len() {
+                0 => println!("{}: ok", solution.bin),
+                n => println!("{}: ok, with {n} warnings", solution.bin),
+            }
+
+            for warning in warnings {
+                println!("  ! {warning}");
+            }
+
+            continue;
+        }
+
+        failed = true;
+
+        println!(
+            "{}: {} violated in {}",
+            solution.bin,
+            violations.len(),
+            path
+        );
+
+        for violation in violations {
+            println!("  - {}: {}", violation.description, violation.reason);
+        }
+    }
+
+    if failed {
+        exit(1);
+    }
+}
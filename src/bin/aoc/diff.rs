@@ -0,0 +1,157 @@
+use std::process::exit;
+
+use itertools::Itertools;
+
+use advent_of_code_2023::answer::Answer;
+use advent_of_code_2023::generators::{generator, DEFAULT_SIZE};
+use advent_of_code_2023::invariants::violations;
+use advent_of_code_2023::registry::{solutions_for, Solution};
+use advent_of_code_2023::rng::Rng;
+
+use crate::run::catch_panic;
+
+const DEFAULT_CASES: u64 = 200;
+
+type Outcome = Result<Vec<Answer>, String>;
+
+fn solve(solution: &Solution, input: &str) -> Outcome {
+    catch_panic(|| {
+        let mut answers = vec![];
+
+        (solution.run)(input, &mut |answer| answers.push(answer));
+
+        answers
+    })
+}
+
+fn valid(solutions: &[&Solution], input: &str) -> bool {
+    solutions
+        .iter()
+        .all(|s| violations(input, s.invariants).is_empty())
+}
+
+fn disagree(solutions: &[&Solution], input: &str) -> bool {
+    !solutions
+        .iter()
+        .map(|solution| solve(solution, input))
+        .all_equal()
+}
+
+/// Smaller variations of `input`: with a line removed, with a column removed and with a
+/// character lowered to `1`.
+pub fn shrink(input: &str) -> Vec<String> {
+    let lines = input.lines().collect_vec();
+    let cols = lines.first().map_or(0, |l| l.chars().count());
+
+    let without_line = (0..lines.len()).map(|i| {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| i != *j)
+            .map(|(_, l)| l)
+            .join("\n")
+    });
+
+    let without_column = (0..cols).map(|i| {
+        lines
+            .iter()
+            .map(|l| {
+                l.chars()
+                    .enumerate()
+                    .filter(|(j, _)| i != *j)
+                    .map(|(_, c)| c)
+                    .collect::<String>()
+            })
+            .join("\n")
+    });
+
+    let lowered = input
+        .char_indices()
+        .filter(|(_, c)| c.is_ascii_digit() && *c > '1')
+        .map(|(i, _)| format!("{}1{}", &input[..i], &input[i + 1..]));
+
+    without_line.chain(without_column).chain(lowered).collect()
+}
+
+/// Greedily applies the first shrink that keeps `interesting` true, until none does.
+pub fn minimize(mut input: String, interesting: impl Fn(&str) -> bool) -> String {
+    while let Some(smaller) = shrink(&input)
+        .into_iter()
+        .find(|candidate| interesting(candidate))
+    {
+        input = smaller;
+    }
+
+    input
+}
+
+fn format_outcome(outcome: &Outcome) -> String {
+    match outcome {
+        Ok(answers) => answers.iter().join(", "),
+        Err(message) => format!("panicked: {message}"),
+    }
+}
+
+/// Runs every implementation of a day on generated inputs, reporting the first input they
+/// disagree on after shrinking it.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let cases = crate::take_option(&mut args, "--cases")
+        .map(|n| n.parse().expect("Cases is not a number"))
+        .unwrap_or(DEFAULT_CASES);
+    let seed = crate::take_option(&mut args, "--seed")
+        .map(|n| n.parse().expect("Seed is not a number"))
+        .unwrap_or(0);
+    let size = crate::take_option(&mut args, "--size")
+        .map(|n| n.parse().expect("Size is not a number"))
+        .unwrap_or(DEFAULT_SIZE);
+
+    let day: u8 = args
+        .first()
+        .expect("Day not found in arguments")
+        .parse()
+        .expect("Day is not a number");
+
+    let solutions = solutions_for(day).collect_vec();
+
+    if solutions.len() < 2 {
+        eprintln!("Day {day} has only one implementation");
+        exit(1);
+    }
+
+    let Some(generate) = generator(day) else {
+        eprintln!("Day {day} has no input generator");
+        exit(1);
+    };
+
+    for case in seed..seed + cases {
+        let input = generate(&mut Rng::new(case), size);
+
+        if !disagree(&solutions, &input) {
+            continue;
+        }
+
+        let input = minimize(input, |candidate| {
+            valid(&solutions, candidate) && disagree(&solutions, candidate)
+        });
+
+        println!("Seed {case} disagrees, minimized to:");
+        println!("{input}");
+        println!();
+
+        for solution in &solutions {
+            println!(
+                "{}: {}",
+                solution.bin,
+                format_outcome(&solve(solution, &input))
+            );
+        }
+
+        exit(1);
+    }
+
+    println!(
+        "{} agree on {cases} inputs",
+        solutions.iter().map(|s| s.bin).join(", ")
+    );
+}
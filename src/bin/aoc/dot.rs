@@ -0,0 +1,34 @@
+use std::fs;
+use std::process::exit;
+
+use advent_of_code_2023::dot::dot_renderer;
+use advent_of_code_2023::invariants;
+use advent_of_code_2023::registry::solutions_for;
+
+/// Prints a day's input, or the given file, as a Graphviz graph.
+pub fn run(args: &[String]) {
+    let day: u8 = args
+        .first()
+        .expect("Day not found in arguments")
+        .parse()
+        .expect("Day is not a number");
+
+    let Some(render) = dot_renderer(day) else {
+        eprintln!("Day {day} has no graph");
+        exit(1);
+    };
+
+    let solution = solutions_for(day).next().unwrap();
+    let path = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| solution.input_path());
+    let input = fs::read_to_string(&path).expect("Cannot read input");
+
+    if let Err(message) = invariants::check(&input, solution.invariants) {
+        eprintln!("{message}");
+        exit(1);
+    }
+
+    print!("{}", render(&input));
+}
@@ -0,0 +1,164 @@
+use std::fs;
+use std::process::exit;
+
+use itertools::Itertools;
+
+use advent_of_code_2023::generators::generator;
+use advent_of_code_2023::registry::{Solution, SOLUTIONS};
+use advent_of_code_2023::rng::Rng;
+
+use crate::diff::minimize;
+use crate::run::catch_panic;
+
+const DEFAULT_CASES: u64 = 2000;
+
+/// Characters that are meaningful in some day's format, plus a few that are in none
+const ALPHABET: &[char] = &[
+    '0', '1', '5', '9', ' ', '\n', ',', ':', ';', '=', '-', '>', '<', '(', ')', '{', '}', '@', '#',
+    '.', '?', '|', '~', '%', '&', 'S', 'A', 'Z', 'x', 'R', 'L', 'U', 'D', 'a', 'é', '→',
+];
+
+/// Numbers that overflow, underflow or divide by zero when they appear where another was expected
+const NUMBERS: &[&str] = &["0", "-1", "4294967296", "99999999999999999999999999999"];
+
+/// The example, if the day has one, and a generated input: valid inputs to mutate.
+fn corpus(solution: &Solution, seed: u64) -> Vec<String> {
+    let example = fs::read_to_string(solution.example_path()).ok();
+    let generated = generator(solution.day).map(|generate| generate(&mut Rng::new(seed), 5));
+
+    example.into_iter().chain(generated).collect()
+}
+
+fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars = input.chars().collect_vec();
+
+    for _ in 0..rng.range(1..=4) {
+        let at = rng.index(chars.len() + 1);
+
+        match rng.range(0..=6) {
+            0 if at < chars.len() => chars[at] = *rng.choose(ALPHABET),
+            1 => chars.insert(at, *rng.choose(ALPHABET)),
+            2 => {
+                let end = (at + rng.index(20)).min(chars.len());
+                chars.drain(at..end);
+            }
+            3 => chars.truncate(at),
+            4 => {
+                // Replaces the number that starts at or after `at`
+                let Some(start) = (at..chars.len()).find(|&i| chars[i].is_ascii_digit()) else {
+                    continue;
+                };
+                let end = (start..chars.len())
+                    .find(|&i| !chars[i].is_ascii_digit())
+                    .unwrap_or(chars.len());
+
+                chars.splice(start..end, rng.choose(NUMBERS).chars());
+            }
+            _ => {
+                let mut lines = chars
+                    .iter()
+                    .collect::<String>()
+                    .lines()
+                    .map(String::from)
+                    .collect_vec();
+
+                if lines.is_empty() {
+                    continue;
+                }
+
+                let line = rng.index(lines.len());
+
+                match rng.range(0..=2) {
+                    0 => lines.insert(line, lines[line].clone()),
+                    1 => drop(lines.remove(line)),
+                    _ => {
+                        let other = rng.index(lines.len());
+                        lines.swap(line, other);
+                    }
+                }
+
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+fn arbitrary(rng: &mut Rng) -> String {
+    (0..rng.range(0..=200))
+        .map(|_| *rng.choose(ALPHABET))
+        .collect()
+}
+
+fn panics(solution: &Solution, input: &str) -> Option<String> {
+    catch_panic(|| solution.parse(input)).err()
+}
+
+/// Feeds mutated examples and arbitrary text to every parser, which must reject what it cannot
+/// parse rather than panic. Reports the first panic of each solution, shrunk to a small input.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let cases = crate::take_option(&mut args, "--cases")
+        .map(|n| n.parse().expect("Cases is not a number"))
+        .unwrap_or(DEFAULT_CASES);
+    let seed = crate::take_option(&mut args, "--seed")
+        .map(|n| n.parse().expect("Seed is not a number"))
+        .unwrap_or(0);
+
+    let day: Option<u8> = args
+        .first()
+        .map(|day| day.parse().expect("Day is not a number"));
+
+    let mut failed = false;
+
+    for solution in SOLUTIONS
+        .iter()
+        .filter(|s| day.is_none_or(|day| s.day == day))
+    {
+        let corpus = corpus(solution, seed);
+        let mut rejected = 0;
+
+        let crash = (seed..seed + cases).find_map(|case| {
+            let mut rng = Rng::new(case);
+            let input = if corpus.is_empty() || rng.chance(1, 10) {
+                arbitrary(&mut rng)
+            } else {
+                let original = rng.choose(&corpus).clone();
+                mutate(&mut rng, &original)
+            };
+
+            match catch_panic(|| solution.parse(&input)) {
+                Ok(Ok(())) => None,
+                Ok(Err(_)) => {
+                    rejected += 1;
+                    None
+                }
+                Err(_) => Some((case, input)),
+            }
+        });
+
+        let Some((case, input)) = crash else {
+            println!(
+                "{}: {cases} inputs, {rejected} rejected, no panics",
+                solution.bin
+            );
+            continue;
+        };
+
+        failed = true;
+
+        let input = minimize(input, |candidate| panics(solution, candidate).is_some());
+
+        println!(
+            "{}: panicked on seed {case}: {}",
+            solution.bin,
+            panics(solution, &input).unwrap_or_default()
+        );
+        println!("  minimized to {input:?}");
+    }
+
+    if failed {
+        exit(1);
+    }
+}
@@ -0,0 +1,70 @@
+use std::fs;
+use std::path::Path;
+use std::process::exit;
+
+use advent_of_code_2023::generators::{generator, DEFAULT_SIZE};
+use advent_of_code_2023::invariants::violations;
+use advent_of_code_2023::registry::solutions_for;
+use advent_of_code_2023::rng::Rng;
+
+/// Prints a generated input for a day, or with `--out <dir>` writes `--count` of them as
+/// `<dir>/<seed>/dayNN.txt`, the layout `aoc multi` reads.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let take_number = |args: &mut Vec<String>, name, default| {
+        crate::take_option(args, name)
+            .map(|n| {
+                n.parse()
+                    .unwrap_or_else(|_| panic!("{name} is not a number"))
+            })
+            .unwrap_or(default)
+    };
+
+    let seed = take_number(&mut args, "--seed", 0);
+    let size = take_number(&mut args, "--size", DEFAULT_SIZE as u64) as usize;
+    let count = take_number(&mut args, "--count", 1);
+    let out = crate::take_option(&mut args, "--out");
+
+    let day: u8 = args
+        .first()
+        .expect("Day not found in arguments")
+        .parse()
+        .expect("Day is not a number");
+
+    let Some(generate) = generator(day) else {
+        eprintln!("Day {day} has no input generator");
+        exit(1);
+    };
+
+    for seed in seed..seed + count {
+        let input = generate(&mut Rng::new(seed), size);
+
+        // A generator that breaks a solution's assumptions is a bug in the generator
+        let mut valid = true;
+
+        for solution in solutions_for(day) {
+            for violation in violations(&input, solution.invariants) {
+                eprintln!(
+                    "Seed {seed} violates {}: {}: {}",
+                    solution.bin, violation.description, violation.reason
+                );
+                valid = false;
+            }
+        }
+
+        if !valid {
+            exit(1);
+        }
+
+        match &out {
+            Some(dir) => {
+                let dir = Path::new(dir).join(seed.to_string());
+
+                fs::create_dir_all(&dir).expect("Cannot create output directory");
+                fs::write(dir.join(format!("day{day:02}.txt")), input + "\n")
+                    .expect("Cannot write input");
+            }
+            None => println!("{input}"),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::Path;
+use std::process::exit;
+
+use advent_of_code_2023::image::renderer;
+use advent_of_code_2023::invariants;
+use advent_of_code_2023::registry::solutions_for;
+use advent_of_code_2023::svg::svg_renderer;
+
+/// Draws a picture of a day's input, or of the given file, as SVG, PNG or PPM by the output's
+/// extension.
+pub fn run(args: &[String]) {
+    let day: u8 = args
+        .first()
+        .expect("Day not found in arguments")
+        .parse()
+        .expect("Day is not a number");
+    let out = args.get(1).expect("Output path not found in arguments");
+
+    let solution = solutions_for(day).next().expect("No solution for that day");
+    let path = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| solution.input_path());
+    let input = fs::read_to_string(&path).expect("Cannot read input");
+
+    if let Err(message) = invariants::check(&input, solution.invariants) {
+        eprintln!("{message}");
+        exit(1);
+    }
+
+    let out_path = Path::new(out);
+
+    if out_path.extension().is_some_and(|ext| ext == "svg") {
+        let Some(render) = svg_renderer(day) else {
+            eprintln!("Day {day} has no SVG image");
+            exit(1);
+        };
+
+        fs::write(out_path, render(&input)).expect("Cannot write image");
+    } else {
+        let Some(render) = renderer(day) else {
+            eprintln!("Day {day} has no image");
+            exit(1);
+        };
+
+        render(&input).save(out_path).expect("Cannot write image");
+    }
+
+    println!("Wrote {out}");
+}
@@ -0,0 +1,113 @@
+use std::env;
+use std::process::exit;
+
+use advent_of_code_2023::allocator::CountingAllocator;
+use dotenv::dotenv;
+
+mod bench;
+mod check;
+mod diff;
+mod dot;
+mod fuzz;
+mod generate;
+mod image;
+mod multi;
+mod props;
+mod report;
+mod run;
+mod show;
+mod status;
+mod submit;
+mod time;
+mod vcd;
+mod watch;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const USAGE: &str = "Usage:
+    aoc status                          Show the 25-day calendar
+    aoc check [day] [input]             Check inputs against each solution's assumptions and warn
+                                        of likely mistakes (19)
+    aoc time [day]                      Time each part and measure its peak heap usage
+    aoc report [day]                    Print answers, verification, timings and input hashes
+    aoc generate <day>                  Print a random input, or write several with --out
+    aoc diff <day>                      Compare a day's implementations on generated inputs
+    aoc fuzz [day]                      Feed mutated examples to the parsers, which must not panic
+    aoc props [day]                     Check optimised functions against brute-force references
+    aoc show <day> [input]              Animate a day's simulation (10, 14, 16 and 17)
+    aoc image <day> <out> [input]       Draw a day's input as .png or .ppm (10, 16, 17 and 18)
+                                        or as .svg (18)
+    aoc dot <day> [input]               Print a day's input as a Graphviz graph (8, 19, 20 and 22)
+    aoc vcd <day> <out> [input]         Record a day's simulation as a waveform (20)
+    aoc bench <day> [input]             Time a day's faster code against what it replaced on a
+                                        large generated input (19)
+    aoc watch <day>                     Rerun a day on its example and input when they, its
+                                        source or the library change, checking known answers
+    aoc multi <dir> [day]               Run against every <dir>/<name>/dayNN.txt, checking
+                                        answers in <dir>/<name>/answers.txt
+    aoc submit <day> <part> <answer>    Submit an answer and record the verdict
+
+Options:
+    --timeout <secs>                    Stop a solution if a part runs longer (time, report, multi, watch)
+    --seed <n>, --size <n>              Seed and scale of generated inputs (generate, diff, props,
+                                        fuzz, bench)
+    --count <n>, --out <dir>            Write n inputs as <dir>/<seed>/dayNN.txt (generate)
+    --cases <n>                         How many generated inputs to try (diff, props, fuzz)
+    --fps <n>, --every <n>              Frames a second, 0 to step with Enter, and how many
+                                        frames to skip between those drawn (show)
+    --presses <n>, --modules <a,b,..>   Button presses to record and the modules to keep,
+                                        all by default (vcd)
+    --stats                             Show time spent parsing and in each part, and the day's
+                                        search counters (time)
+    --format <json|markdown>            Report format, JSON by default (report)";
+
+/// Removes `name <value>` from the arguments, returning the value
+pub fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let i = args.iter().position(|arg| arg == name)?;
+
+    args.remove(i);
+
+    assert!(i < args.len(), "Missing value for {name}");
+
+    Some(args.remove(i))
+}
+
+/// Removes `name` from the arguments, returning whether it was there
+pub fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    let len = args.len();
+
+    args.retain(|arg| arg != name);
+
+    args.len() < len
+}
+
+fn main() {
+    dotenv().ok();
+
+    let args: Vec<_> = env::args().skip(1).collect();
+
+    match args.first().map(|s| s.as_str()) {
+        Some("status") => status::run(),
+        Some("check") => check::run(&args[1..]),
+        Some("time") => time::run(&args[1..]),
+        Some("report") => report::run(&args[1..]),
+        Some("multi") => multi::run(&args[1..]),
+        Some("watch") => watch::run(&args[1..]),
+        Some("diff") => diff::run(&args[1..]),
+        Some("generate") => generate::run(&args[1..]),
+        Some("props") => props::run(&args[1..]),
+        Some("fuzz") => fuzz::run(&args[1..]),
+        Some("show") => show::run(&args[1..]),
+        Some("image") => image::run(&args[1..]),
+        Some("dot") => dot::run(&args[1..]),
+        Some("vcd") => vcd::run(&args[1..]),
+        Some("bench") => bench::run(&args[1..]),
+        Some(run::SOLVE_COMMAND) => run::solve(&args[1..]),
+        Some("submit") => submit::run(&args[1..]),
+        _ => {
+            eprintln!("{USAGE}");
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::process::exit;
+use std::time::Duration;
+
+use itertools::Itertools;
+
+use advent_of_code_2023::answer::Answer;
+use advent_of_code_2023::answers::Answers;
+use advent_of_code_2023::invariants::violations;
+use advent_of_code_2023::registry::{Solution, SOLUTIONS};
+
+use crate::run::{run_isolated, take_timeout, Failure};
+
+enum Outcome {
+    Correct,
+    Unverified(Vec<Answer>),
+    Wrong(Vec<String>),
+    Invalid(Vec<String>),
+    Panicked(String),
+    TimedOut(usize, Duration),
+    Missing,
+}
+
+impl Outcome {
+    fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Wrong(_)
+                | Outcome::Invalid(_)
+                | Outcome::Panicked(_)
+                | Outcome::TimedOut(_, _)
+        )
+    }
+}
+
+fn run_solution(solution: &Solution, dir: &Path, answers: &Answers, timeout: Duration) -> Outcome {
+    let path = dir.join(format!("day{:02}.txt", solution.day));
+
+    let Ok(input) = fs::read_to_string(&path) else {
+        return Outcome::Missing;
+    };
+
+    let violations = violations(&input, solution.invariants);
+
+    if !violations.is_empty() {
+        return Outcome::Invalid(
+            violations
+                .iter()
+                .map(|v| format!("{}: {}", v.description, v.reason))
+                .collect(),
+        );
+    }
+
+    let result = run_isolated(solution, &path, timeout);
+
+    match result.failure {
+        Some(Failure::Panicked(message)) => return Outcome::Panicked(message),
+        Some(Failure::TimedOut(timeout)) => {
+            return Outcome::TimedOut(result.parts.len() + 1, timeout)
+        }
+        None => {}
+    }
+
+    let results = result.parts.into_iter().map(|p| p.answer).collect_vec();
+
+    let mut wrong = vec![];
+    let mut verified = true;
+
+    for (part, result) in (1..).zip(&results) {
+        match answers.get(solution.day, part) {
+            Some(expected) if expected != result => {
+                wrong.push(format!("part {part}: got {result}, expected {expected}"))
+            }
+            Some(_) => {}
+            None => verified = false,
+        }
+    }
+
+    if !wrong.is_empty() {
+        Outcome::Wrong(wrong)
+    } else if verified {
+        Outcome::Correct
+    } else {
+        Outcome::Unverified(results)
+    }
+}
+
+fn print_failure(solution: &Solution, kind: &str, reasons: &[String]) {
+    println!("{}: {}", solution.bin, kind);
+
+    for reason in reasons {
+        println!("  - {reason}");
+    }
+}
+
+/// Runs every solution against each `<dir>/<name>/dayNN.txt`, checking the results against
+/// `<dir>/<name>/answers.txt`.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let timeout = take_timeout(&mut args);
+
+    let dir = args
+        .first()
+        .expect("Inputs directory not found in arguments");
+    let day: Option<u8> = args
+        .get(1)
+        .map(|day| day.parse().expect("Day is not a number"));
+
+    let inputs = fs::read_dir(dir)
+        .expect("Cannot read inputs directory")
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.path())
+        .sorted()
+        .collect_vec();
+
+    let mut failures: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+    for input_dir in inputs {
+        let name = input_dir.file_name().unwrap().to_string_lossy().to_string();
+        let answers = Answers::load(input_dir.join("answers.txt"));
+
+        println!("--- {name} ---");
+
+        for solution in SOLUTIONS
+            .iter()
+            .filter(|s| day.is_none_or(|day| s.day == day))
+        {
+            let outcome = run_solution(solution, &input_dir, &answers, timeout);
+
+            match &outcome {
+                Outcome::Correct => println!("{}: ok", solution.bin),
+                Outcome::Unverified(results) => {
+                    println!("{}: unverified {}", solution.bin, results.iter().join(", "))
+                }
+                Outcome::Wrong(reasons) => print_failure(solution, "wrong answer", reasons),
+                Outcome::Invalid(reasons) => print_failure(solution, "invalid input", reasons),
+                Outcome::TimedOut(part, timeout) => {
+                    println!(
+                        "{}: part {part} timed out after {timeout:.2?}",
+                        solution.bin
+                    )
+                }
+                Outcome::Panicked(message) => {
+                    println!("{}: panicked: {}", solution.bin, message)
+                }
+                Outcome::Missing => {}
+            }
+
+            if outcome.is_failure() {
+                failures.entry(solution.bin).or_default().push(name.clone());
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("Failing solutions:");
+
+    for (bin, names) in failures {
+        println!("{bin}: {}", names.join(", "));
+    }
+
+    exit(1);
+}
@@ -0,0 +1,55 @@
+use std::process::exit;
+
+use advent_of_code_2023::properties::properties_for;
+use advent_of_code_2023::rng::Rng;
+
+use crate::run::catch_panic;
+
+const DEFAULT_CASES: u64 = 500;
+
+/// Checks each day's optimised functions against their references on generated cases, stopping
+/// a property at its first counterexample.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let cases = crate::take_option(&mut args, "--cases")
+        .map(|n| n.parse().expect("Cases is not a number"))
+        .unwrap_or(DEFAULT_CASES);
+    let seed = crate::take_option(&mut args, "--seed")
+        .map(|n| n.parse().expect("Seed is not a number"))
+        .unwrap_or(0);
+
+    let day: Option<u8> = args
+        .first()
+        .map(|day| day.parse().expect("Day is not a number"));
+
+    let mut failed = false;
+
+    for day in (1..=25).filter(|d| day.is_none_or(|day| *d == day)) {
+        for property in properties_for(day) {
+            let counterexample = (seed..seed + cases).find_map(|case| {
+                match catch_panic(|| (property.check)(&mut Rng::new(case))) {
+                    Ok(Ok(())) => None,
+                    Ok(Err(reason)) => Some((case, reason)),
+                    Err(message) => Some((case, format!("panicked: {message}"))),
+                }
+            });
+
+            match counterexample {
+                None => println!("day{day:02}: {}: ok", property.description),
+                Some((case, reason)) => {
+                    failed = true;
+
+                    println!(
+                        "day{day:02}: {}: failed on seed {case}",
+                        property.description
+                    );
+                    println!("  {}", reason.replace('\n', "\n  "));
+                }
+            }
+        }
+    }
+
+    if failed {
+        exit(1);
+    }
+}
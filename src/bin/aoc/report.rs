@@ -0,0 +1,268 @@
+use std::fs;
+use std::process::exit;
+use std::time::Duration;
+
+use itertools::Itertools;
+use sha2::{Digest, Sha256};
+
+use advent_of_code_2023::allocator::format_bytes;
+use advent_of_code_2023::answer::Answer;
+use advent_of_code_2023::answers::{Answers, ANSWERS_PATH};
+use advent_of_code_2023::registry::{Solution, SOLUTIONS};
+
+use crate::run::{run_isolated, take_timeout, Failure};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Status {
+    Correct,
+    Wrong,
+    Unverified,
+    Panicked,
+    TimedOut,
+    NotRun,
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unverified => "unverified",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed_out",
+            Status::NotRun => "not_run",
+        }
+    }
+}
+
+struct PartReport {
+    part: u8,
+    status: Status,
+    answer: Option<Answer>,
+    expected: Option<Answer>,
+    elapsed: Option<Duration>,
+    peak: Option<usize>,
+    /// The part this one was solved together with, whose time and peak heap include it
+    timed_with: Option<u8>,
+}
+
+struct SolutionReport {
+    solution: &'static Solution,
+    input_sha256: String,
+    parts: Vec<PartReport>,
+    failure: Option<String>,
+    warnings: Vec<String>,
+}
+
+fn report_solution(
+    solution: &'static Solution,
+    input: &[u8],
+    answers: &Answers,
+    timeout: Duration,
+) -> SolutionReport {
+    let result = run_isolated(solution, solution.input_path().as_ref(), timeout);
+    let warnings = result.warnings().map(|w| w.to_string()).collect();
+    // The part that was running when the solver failed, later parts never started
+    let failed_part = result.parts.len() as u8 + 1;
+    let mut results = result.parts.into_iter();
+
+    let parts = (1..=solution.parts)
+        .map(|part| {
+            let expected = answers.get(solution.day, part).cloned();
+
+            let Some(result) = results.next() else {
+                let status = match &result.failure {
+                    Some(Failure::Panicked(_)) if part == failed_part => Status::Panicked,
+                    Some(Failure::TimedOut(_)) if part == failed_part => Status::TimedOut,
+                    _ => Status::NotRun,
+                };
+
+                return PartReport {
+                    part,
+                    status,
+                    answer: None,
+                    expected,
+                    elapsed: None,
+                    peak: None,
+                    timed_with: None,
+                };
+            };
+
+            let status = match &expected {
+                Some(expected) if *expected == result.answer => Status::Correct,
+                Some(_) => Status::Wrong,
+                None => Status::Unverified,
+            };
+
+            PartReport {
+                part,
+                status,
+                answer: Some(result.answer),
+                expected,
+                timed_with: result.elapsed.is_none().then_some(part - 1),
+                elapsed: result.elapsed,
+                peak: result.peak,
+            }
+        })
+        .collect();
+
+    SolutionReport {
+        solution,
+        input_sha256: format!("{:x}", Sha256::digest(input)),
+        parts,
+        warnings,
+        failure: result.failure.map(|failure| match failure {
+            Failure::Panicked(message) => format!("panicked: {message}"),
+            Failure::TimedOut(timeout) => format!("timed out after {timeout:.2?}"),
+        }),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+
+    escaped.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn json_or_null<T>(value: Option<T>, f: impl Fn(T) -> String) -> String {
+    value.map_or("null".to_string(), f)
+}
+
+/// Answers are written as strings, since they can be too large for a JSON number to hold exactly.
+fn json(reports: &[SolutionReport]) -> String {
+    let solutions = reports
+        .iter()
+        .map(|report| {
+            let parts = report
+                .parts
+                .iter()
+                .map(|part| {
+                    format!(
+                        "{{\"part\": {}, \"status\": {}, \"answer\": {}, \"expected\": {}, \
+                         \"elapsed_ns\": {}, \"peak_heap_bytes\": {}, \"timed_with_part\": {}}}",
+                        part.part,
+                        json_string(part.status.name()),
+                        json_or_null(part.answer.as_ref(), |a| json_string(&a.to_string())),
+                        json_or_null(part.expected.as_ref(), |a| json_string(&a.to_string())),
+                        json_or_null(part.elapsed, |e| e.as_nanos().to_string()),
+                        json_or_null(part.peak, |p| p.to_string()),
+                        json_or_null(part.timed_with, |p| p.to_string()),
+                    )
+                })
+                .join(",\n        ");
+
+            format!(
+                "    {{\n      \"day\": {},\n      \"bin\": {},\n      \"input_sha256\": {},\n      \
+                 \"failure\": {},\n      \"warnings\": [{}],\n      \
+                 \"parts\": [\n        {}\n      ]\n    }}",
+                report.solution.day,
+                json_string(report.solution.bin),
+                json_string(&report.input_sha256),
+                json_or_null(report.failure.as_deref(), json_string),
+                report.warnings.iter().map(|w| json_string(w)).join(", "),
+                parts
+            )
+        })
+        .join(",\n");
+
+    format!("{{\n  \"solutions\": [\n{solutions}\n  ]\n}}")
+}
+
+fn markdown(reports: &[SolutionReport]) -> String {
+    let mut lines = vec![
+        "| Day | Solution | Part | Answer | Status | Time | Peak heap |".to_string(),
+        "| ---: | --- | ---: | ---: | --- | ---: | ---: |".to_string(),
+    ];
+
+    for report in reports {
+        for part in &report.parts {
+            let shared = |with: u8| format!("with part {with}");
+
+            lines.push(format!(
+                "| {} | {} | {} | {} | {} | {} | {} |",
+                report.solution.day,
+                report.solution.bin,
+                part.part,
+                part.answer
+                    .as_ref()
+                    .map(|a| a.to_string())
+                    .unwrap_or_default(),
+                part.status.name().replace('_', " "),
+                part.elapsed
+                    .map(|e| format!("{e:.2?}"))
+                    .or(part.timed_with.map(shared))
+                    .unwrap_or_default(),
+                part.peak
+                    .map(format_bytes)
+                    .or(part.timed_with.map(shared))
+                    .unwrap_or_default(),
+            ));
+        }
+    }
+
+    // Warnings don't belong to a part, so they follow the table
+    let warnings = reports
+        .iter()
+        .flat_map(|report| {
+            report
+                .warnings
+                .iter()
+                .map(|warning| format!("- {}: {warning}", report.solution.bin))
+        })
+        .collect_vec();
+
+    if !warnings.is_empty() {
+        lines.push(String::new());
+        lines.push("Warnings:".to_string());
+        lines.push(String::new());
+        lines.extend(warnings);
+    }
+
+    lines.join("\n")
+}
+
+/// Runs solutions against their own inputs and prints the results as JSON or a Markdown table.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let timeout = take_timeout(&mut args);
+    let format = crate::take_option(&mut args, "--format").unwrap_or("json".to_string());
+
+    let day: Option<u8> = args
+        .first()
+        .map(|day| day.parse().expect("Day is not a number"));
+
+    let answers = Answers::load(ANSWERS_PATH);
+
+    let reports = SOLUTIONS
+        .iter()
+        .filter(|s| day.is_none_or(|day| s.day == day))
+        .filter_map(|solution| {
+            let input = fs::read(solution.input_path()).ok()?;
+
+            Some(report_solution(solution, &input, &answers, timeout))
+        })
+        .collect_vec();
+
+    match format.as_str() {
+        "json" => println!("{}", json(&reports)),
+        "markdown" | "md" => println!("{}", markdown(&reports)),
+        _ => {
+            eprintln!("Unknown format {format}, expected json or markdown");
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use std::any::Any;
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2023::allocator;
+use advent_of_code_2023::answer::Answer;
+use advent_of_code_2023::registry::{Solution, SOLUTIONS};
+use advent_of_code_2023::stats::{self, SpanStats};
+
+/// Hidden subcommand used to solve an input in a child process
+pub const SOLVE_COMMAND: &str = "__solve";
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Runs `f`, turning a panic into its message instead of printing it and unwinding further.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message);
+
+    panic::set_hook(hook);
+
+    result
+}
+
+/// Removes `--timeout <secs>` from the arguments, falling back to the default.
+pub fn take_timeout(args: &mut Vec<String>) -> Duration {
+    crate::take_option(args, "--timeout")
+        .map(|secs| Duration::from_secs_f64(secs.parse().expect("Timeout is not a number")))
+        .unwrap_or(DEFAULT_TIMEOUT)
+}
+
+pub struct PartResult {
+    pub answer: Answer,
+    /// The time and peak heap usage of the part, or `None` when it was solved together with the
+    /// previous part, which they are counted in
+    pub elapsed: Option<Duration>,
+    pub peak: Option<usize>,
+}
+
+pub enum Failure {
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+pub struct RunResult {
+    pub parts: Vec<PartResult>,
+    pub failure: Option<Failure>,
+    /// The tracing spans the solution went through, with their counters and warnings
+    pub spans: Vec<SpanStats>,
+}
+
+impl RunResult {
+    /// Warnings from every span, such as a day falling back to a slower method
+    pub fn warnings(&self) -> impl Iterator<Item = &str> {
+        self.spans
+            .iter()
+            .flat_map(|span| span.warnings.iter().map(|w| w.as_str()))
+    }
+}
+
+/// Child side of `run_isolated`: solves the input, as an example with `--example`, printing each
+/// part as it completes with the time and peak heap usage since the previous part, or `-` for the
+/// second of combined parts, then the spans it went through, each followed by its warnings.
+pub fn solve(args: &[String]) {
+    let mut args = args.to_vec();
+    let example = crate::take_flag(&mut args, "--example");
+
+    let [bin, path] = &args[..] else {
+        panic!("Expected <solution> <input>");
+    };
+
+    let solution = SOLUTIONS
+        .iter()
+        .find(|s| s.bin == bin)
+        .expect("Unknown solution");
+    let input = fs::read_to_string(path).expect("Cannot read input");
+    let run = match solution.example_run() {
+        Some(run) if example => run,
+        _ => solution.run,
+    };
+
+    allocator::reset_peak();
+    let mut baseline = allocator::current();
+    let mut start = Instant::now();
+    let mut reported = 0;
+
+    let (result, spans) = stats::collect(|| {
+        catch_panic(|| {
+            run(&input, &mut |answer| {
+                let elapsed = start.elapsed();
+                let peak = allocator::peak().saturating_sub(baseline);
+
+                reported += 1;
+
+                if solution.combined && reported == 2 {
+                    println!("part\t-\t-\t{answer}");
+                } else {
+                    println!("part\t{}\t{}\t{}", elapsed.as_nanos(), peak, answer);
+                }
+
+                allocator::reset_peak();
+                baseline = allocator::current();
+                start = Instant::now();
+            })
+        })
+    });
+
+    for span in spans {
+        let counters = span
+            .counters
+            .iter()
+            .map(|(name, n)| format!("{name}={n}"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        println!(
+            "span\t{}\t{}\t{}\t{}",
+            span.depth,
+            span.elapsed.as_nanos(),
+            span.name,
+            counters
+        );
+
+        for warning in span.warnings {
+            println!("warning\t{}", warning.replace('\n', " "));
+        }
+    }
+
+    if let Err(message) = result {
+        println!("panic\t{}", message.replace('\n', " "));
+    }
+}
+
+fn parse_part(line: &str) -> Option<PartResult> {
+    let mut fields = line.splitn(4, '\t').skip(1);
+
+    Some(PartResult {
+        elapsed: match fields.next()? {
+            "-" => None,
+            nanos => Some(Duration::from_nanos(nanos.parse().ok()?)),
+        },
+        peak: match fields.next()? {
+            "-" => None,
+            peak => Some(peak.parse().ok()?),
+        },
+        answer: fields.next()?.parse().ok()?,
+    })
+}
+
+fn parse_span(line: &str) -> Option<SpanStats> {
+    let mut fields = line.splitn(5, '\t').skip(1);
+
+    Some(SpanStats {
+        depth: fields.next()?.parse().ok()?,
+        elapsed: Duration::from_nanos(fields.next()?.parse().ok()?),
+        name: fields.next()?.to_string(),
+        counters: fields
+            .next()?
+            .split(',')
+            .filter(|counter| !counter.is_empty())
+            .map(|counter| {
+                let (name, n) = counter.split_once('=')?;
+
+                Some((name.to_string(), n.parse().ok()?))
+            })
+            .collect::<Option<_>>()?,
+        warnings: vec![],
+    })
+}
+
+/// Solves `input` with `solution` in a child process, which is killed if any single part takes
+/// longer than `timeout`.
+pub fn run_isolated(solution: &Solution, input: &Path, timeout: Duration) -> RunResult {
+    run_isolated_with(
+        &env::current_exe().unwrap(),
+        solution,
+        input,
+        false,
+        timeout,
+    )
+}
+
+/// Like `run_isolated`, but solving with the given build of this binary, and as the day's example
+/// if `example` is set.
+pub fn run_isolated_with(
+    exe: &Path,
+    solution: &Solution,
+    input: &Path,
+    example: bool,
+    timeout: Duration,
+) -> RunResult {
+    let mut child = Command::new(exe)
+        .arg(SOLVE_COMMAND)
+        .arg(solution.bin)
+        .arg(input)
+        .args(example.then_some("--example"))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("Cannot start solver");
+
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut result = RunResult {
+        parts: vec![],
+        failure: None,
+        spans: vec![],
+    };
+
+    loop {
+        match rx.recv_timeout(timeout) {
+            Ok(line) if line.starts_with("part\t") => result.parts.extend(parse_part(&line)),
+            Ok(line) if line.starts_with("span\t") => result.spans.extend(parse_span(&line)),
+            Ok(line) if line.starts_with("warning\t") => {
+                if let Some(span) = result.spans.last_mut() {
+                    span.warnings.push(line["warning\t".len()..].to_string());
+                }
+            }
+            Ok(line) => {
+                let message = line.strip_prefix("panic\t").unwrap_or(&line);
+                result.failure = Some(Failure::Panicked(message.to_string()));
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                child.kill().ok();
+                result.failure = Some(Failure::TimedOut(timeout));
+                break;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    let status = child.wait().expect("Solver was not running");
+
+    if result.failure.is_none() && !status.success() {
+        result.failure = Some(Failure::Panicked(format!("solver exited with {status}")));
+    }
+
+    result
+}
@@ -0,0 +1,44 @@
+use std::fs;
+use std::process::exit;
+
+use advent_of_code_2023::invariants;
+use advent_of_code_2023::registry::solutions_for;
+use advent_of_code_2023::visual::{visualiser, Animation};
+
+const DEFAULT_FPS: f64 = 30.0;
+
+/// Animates a day's simulation on its input, or on the given file.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let fps = crate::take_option(&mut args, "--fps")
+        .map(|n| n.parse().expect("Frames per second is not a number"))
+        .unwrap_or(DEFAULT_FPS);
+    let every = crate::take_option(&mut args, "--every")
+        .map(|n| n.parse().expect("Every is not a number"))
+        .unwrap_or(1);
+
+    let day: u8 = args
+        .first()
+        .expect("Day not found in arguments")
+        .parse()
+        .expect("Day is not a number");
+
+    let Some(visualise) = visualiser(day) else {
+        eprintln!("Day {day} has no visualisation");
+        exit(1);
+    };
+
+    let solution = solutions_for(day).next().unwrap();
+    let path = args
+        .get(1)
+        .cloned()
+        .unwrap_or_else(|| solution.input_path());
+    let input = fs::read_to_string(&path).expect("Cannot read input");
+
+    if let Err(message) = invariants::check(&input, solution.invariants) {
+        eprintln!("{message}");
+        exit(1);
+    }
+
+    visualise(&input, &mut Animation::new(fps).every(every));
+}
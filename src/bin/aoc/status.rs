@@ -0,0 +1,83 @@
+use itertools::Itertools;
+
+use advent_of_code_2023::answers::{Answers, ANSWERS_PATH};
+use advent_of_code_2023::history::{format_duration, History, HISTORY_PATH};
+use advent_of_code_2023::registry::solutions_for;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum PartStatus {
+    Star,
+    Unverified,
+    Missing,
+}
+
+impl PartStatus {
+    fn symbol(&self) -> char {
+        match self {
+            PartStatus::Star => '*',
+            PartStatus::Unverified => '+',
+            PartStatus::Missing => '.',
+        }
+    }
+}
+
+fn part_status(day: u8, part: u8, answers: &Answers, history: &History) -> PartStatus {
+    if answers.get(day, part).is_some() || history.first_correct(day, part).is_some() {
+        PartStatus::Star
+    } else if solutions_for(day).any(|s| s.parts >= part) {
+        PartStatus::Unverified
+    } else {
+        PartStatus::Missing
+    }
+}
+
+pub fn run() {
+    let answers = Answers::load(ANSWERS_PATH);
+    let history = History::load(HISTORY_PATH);
+
+    let mut calendar = (1..=25)
+        .map(|day| [1, 2].map(|part| part_status(day, part, &answers, &history)))
+        .collect_vec();
+
+    // The last star, day 25 part 2, is awarded for free once the other 49 are collected
+    if calendar
+        .iter()
+        .flatten()
+        .take(49)
+        .all(|s| *s == PartStatus::Star)
+    {
+        calendar[24][1] = PartStatus::Star;
+    }
+
+    println!("--- Advent of Code 2023 ---");
+    println!("Day  Stars  Part 1       Part 2       Solutions");
+
+    for (day, statuses) in (1..=25).zip(calendar.iter()) {
+        let times = [1, 2].map(|part| {
+            history
+                .solve_time(day, part)
+                .map(format_duration)
+                .unwrap_or_default()
+        });
+
+        println!(
+            "{:>3}  {}{}     {:<12} {:<12} {}",
+            day,
+            statuses[0].symbol(),
+            statuses[1].symbol(),
+            times[0],
+            times[1],
+            solutions_for(day).map(|s| s.bin).join(", ")
+        );
+    }
+
+    let count = |status| calendar.iter().flatten().filter(|s| **s == status).count();
+
+    println!();
+    println!("Stars: {}/50", count(PartStatus::Star));
+    println!(
+        "Implemented but unverified: {}",
+        count(PartStatus::Unverified)
+    );
+    println!("* star earned, + implemented but unverified, . not started");
+}
@@ -0,0 +1,30 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use advent_of_code_2023::client::submit_answer;
+use advent_of_code_2023::history::{History, Submission, HISTORY_PATH};
+
+pub fn run(args: &[String]) {
+    let [day, part, answer] = args else {
+        panic!("Expected <day> <part> <answer>");
+    };
+
+    let day: u8 = day.parse().expect("Day is not a number");
+    let part: u8 = part.parse().expect("Part is not a number");
+
+    let verdict = submit_answer(day, part, answer);
+
+    let submission = Submission {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs(),
+        day,
+        part,
+        verdict,
+        answer: answer.clone(),
+    };
+
+    History::append(HISTORY_PATH, &submission).expect("Error writing history");
+
+    println!("Day {day:02} part {part}: {verdict}");
+}
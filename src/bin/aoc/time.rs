@@ -0,0 +1,93 @@
+use std::path::Path;
+use std::process::exit;
+
+use advent_of_code_2023::allocator::format_bytes;
+use advent_of_code_2023::answers::{Answers, ANSWERS_PATH};
+use advent_of_code_2023::registry::SOLUTIONS;
+
+use crate::run::{run_isolated, take_timeout, Failure};
+
+/// Runs solutions against their own inputs, reporting each part's time and peak heap usage, and
+/// with `--stats` the time spent parsing and in each part along with the day's counters.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let timeout = take_timeout(&mut args);
+    let show_stats = crate::take_flag(&mut args, "--stats");
+
+    let day: Option<u8> = args
+        .first()
+        .map(|day| day.parse().expect("Day is not a number"));
+
+    let answers = Answers::load(ANSWERS_PATH);
+    let mut failed = false;
+
+    for solution in SOLUTIONS
+        .iter()
+        .filter(|s| day.is_none_or(|day| s.day == day))
+    {
+        let path = solution.input_path();
+
+        if !Path::new(&path).exists() {
+            continue;
+        }
+
+        println!("--- {} ---", solution.bin);
+
+        let result = run_isolated(solution, Path::new(&path), timeout);
+
+        for (part, result) in (1..).zip(&result.parts) {
+            let verdict = match answers.get(solution.day, part) {
+                Some(expected) if *expected == result.answer => "ok".to_string(),
+                Some(expected) => {
+                    failed = true;
+                    format!("wrong, expected {expected}")
+                }
+                None => "unverified".to_string(),
+            };
+
+            match (result.elapsed, result.peak) {
+                (Some(elapsed), Some(peak)) => println!(
+                    "Part {}: {} ({}) took {:.2?}, peak heap {}",
+                    part,
+                    result.answer,
+                    verdict,
+                    elapsed,
+                    format_bytes(peak)
+                ),
+                _ => println!(
+                    "Part {}: {} ({}) solved and timed together with part {}",
+                    part,
+                    result.answer,
+                    verdict,
+                    part - 1
+                ),
+            }
+        }
+
+        for warning in result.warnings() {
+            println!("Warning: {warning}");
+        }
+
+        if show_stats {
+            for span in &result.spans {
+                println!("{}{span}", "  ".repeat(span.depth + 1));
+            }
+        }
+
+        let next_part = result.parts.len() + 1;
+
+        match result.failure {
+            Some(Failure::Panicked(message)) => println!("Part {next_part} panicked: {message}"),
+            Some(Failure::TimedOut(timeout)) => {
+                println!("Part {next_part} timed out after {timeout:.2?}")
+            }
+            None => continue,
+        }
+
+        failed = true;
+    }
+
+    if failed {
+        exit(1);
+    }
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use std::process::exit;
+
+use advent_of_code_2023::invariants;
+use advent_of_code_2023::registry::solutions_for;
+use advent_of_code_2023::vcd::vcd_recorder;
+
+const DEFAULT_PRESSES: usize = 1000;
+
+/// Records a day's simulation on its input, or on the given file, as a Value Change Dump.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let presses = crate::take_option(&mut args, "--presses")
+        .map(|n| n.parse().expect("Presses is not a number"))
+        .unwrap_or(DEFAULT_PRESSES);
+    let modules: Vec<String> = crate::take_option(&mut args, "--modules")
+        .map(|names| names.split(',').map(|name| name.to_string()).collect())
+        .unwrap_or_default();
+
+    let day: u8 = args
+        .first()
+        .expect("Day not found in arguments")
+        .parse()
+        .expect("Day is not a number");
+    let out = args.get(1).expect("Output path not found in arguments");
+
+    let Some(record) = vcd_recorder(day) else {
+        eprintln!("Day {day} has no waveform");
+        exit(1);
+    };
+
+    let solution = solutions_for(day).next().unwrap();
+    let path = args
+        .get(2)
+        .cloned()
+        .unwrap_or_else(|| solution.input_path());
+    let input = fs::read_to_string(&path).expect("Cannot read input");
+
+    if let Err(message) = invariants::check(&input, solution.invariants) {
+        eprintln!("{message}");
+        exit(1);
+    }
+
+    match record(&input, presses, &modules) {
+        Ok(vcd) => fs::write(out, vcd).expect("Cannot write waveform"),
+        Err(message) => {
+            eprintln!("{message}");
+            exit(1);
+        }
+    }
+
+    println!("Wrote {out}");
+}
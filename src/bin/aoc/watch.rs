@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use itertools::Itertools;
+
+use advent_of_code_2023::answer::Answer;
+use advent_of_code_2023::answers::{Answers, ANSWERS_PATH, EXAMPLE_ANSWERS_PATH};
+use advent_of_code_2023::invariants::violations;
+use advent_of_code_2023::registry::{solutions_for, Solution, SOLUTIONS};
+
+use crate::run::{run_isolated_with, take_timeout, Failure};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const LIBRARY_DIR: &str = "./src";
+
+fn sources(dir: &str) -> impl Iterator<Item = PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+}
+
+/// The solutions' sources and inputs, and the library's sources, which they are built with
+fn watched_files(solutions: &[&Solution]) -> Vec<PathBuf> {
+    solutions
+        .iter()
+        .flat_map(|solution| {
+            sources(&solution.source_dir()).chain([
+                PathBuf::from(solution.input_path()),
+                PathBuf::from(solution.example_path()),
+            ])
+        })
+        .chain(sources(LIBRARY_DIR))
+        .unique()
+        .collect()
+}
+
+/// Modification times of the watched files, with `None` for files that do not exist (yet).
+fn snapshot(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Rebuilds this binary in the profile it was built with, letting cargo print any errors.
+fn rebuild(exe: &Path) -> bool {
+    let mut command = Command::new("cargo");
+
+    command.args(["build", "--quiet", "--bin", "aoc"]);
+
+    if exe.components().any(|c| c.as_os_str() == "release") {
+        command.arg("--release");
+    }
+
+    command.status().is_ok_and(|status| status.success())
+}
+
+fn run_input(
+    exe: &Path,
+    solution: &Solution,
+    path: &str,
+    example: bool,
+    expected: impl Fn(u8) -> Option<Answer>,
+    last: &mut HashMap<(&'static str, String, u8), Answer>,
+    timeout: Duration,
+) {
+    if !Path::new(path).exists() {
+        return;
+    }
+
+    let name = Path::new(path).file_name().unwrap().to_string_lossy();
+
+    println!("{} on {}:", solution.bin, name);
+
+    // Examples solved with their own parameters needn't keep to the input's invariants
+    if !example || solution.example_run().is_none() {
+        let input = fs::read_to_string(path).unwrap_or_default();
+        let violations = violations(&input, solution.invariants);
+
+        if !violations.is_empty() {
+            println!("  skipped, it breaks the solution's assumptions:");
+
+            for violation in violations {
+                println!("    - {}: {}", violation.description, violation.reason);
+            }
+
+            return;
+        }
+    }
+
+    let result = run_isolated_with(exe, solution, Path::new(path), example, timeout);
+
+    for (part, result) in (1..).zip(&result.parts) {
+        let key = (solution.bin, name.to_string(), part);
+
+        let change = match last.get(&key) {
+            None => String::new(),
+            Some(previous) if *previous == result.answer => " unchanged".to_string(),
+            Some(previous) => format!(" changed from {previous}"),
+        };
+
+        let verdict = match expected(part) {
+            Some(expected) if expected == result.answer => " ok",
+            Some(_) => " wrong",
+            None => "",
+        };
+
+        let elapsed = match result.elapsed {
+            Some(elapsed) => format!("{elapsed:.2?}"),
+            None => format!("with part {}", part - 1),
+        };
+
+        println!(
+            "  Part {}: {}{}{} ({})",
+            part, result.answer, verdict, change, elapsed
+        );
+
+        last.insert(key, result.answer.clone());
+    }
+
+    for warning in result.warnings() {
+        println!("  warning: {warning}");
+    }
+
+    match result.failure {
+        Some(Failure::Panicked(message)) => println!("  panicked: {message}"),
+        Some(Failure::TimedOut(timeout)) => println!("  timed out after {timeout:.2?}"),
+        None => {}
+    }
+}
+
+/// Reruns a day's solutions on its example then its real input whenever the source, the library
+/// or either input changes, checking both against their known answers and showing how the answers
+/// moved since the previous run.
+pub fn run(args: &[String]) {
+    let mut args = args.to_vec();
+    let timeout = take_timeout(&mut args);
+
+    let target = args.first().expect("Day not found in arguments");
+    let solutions = match target.parse() {
+        Ok(day) => solutions_for(day).collect_vec(),
+        Err(_) => SOLUTIONS.iter().filter(|s| s.bin == target).collect_vec(),
+    };
+
+    assert!(!solutions.is_empty(), "No solution for {target}");
+
+    // Rebuilding may replace the running binary, so remember where it lives before it does
+    let exe = env::current_exe().unwrap();
+    let files = watched_files(&solutions);
+    let mut last = HashMap::new();
+    let mut seen = None;
+
+    println!(
+        "Watching {}",
+        files.iter().map(|path| path.display()).join(", ")
+    );
+
+    loop {
+        let current = snapshot(&files);
+
+        if seen.as_ref() == Some(&current) {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+
+        seen = Some(current);
+
+        println!();
+        println!("--- Rebuilding ---");
+
+        if !rebuild(&exe) {
+            println!("Build failed, waiting for changes");
+            continue;
+        }
+
+        let answers = Answers::load(ANSWERS_PATH);
+        let example_answers = Answers::load(EXAMPLE_ANSWERS_PATH);
+
+        for solution in &solutions {
+            run_input(
+                &exe,
+                solution,
+                &solution.example_path(),
+                true,
+                |part| example_answers.get(solution.day, part).cloned(),
+                &mut last,
+                timeout,
+            );
+            run_input(
+                &exe,
+                solution,
+                &solution.input_path(),
+                false,
+                |part| answers.get(solution.day, part).cloned(),
+                &mut last,
+                timeout,
+            );
+        }
+    }
+}
@@ -1,18 +1,50 @@
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
 use itertools::Itertools;
 
 type Int = u32;
 type InputType = Vec<String>;
 
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "every line is ASCII, as lines are sliced by byte offset",
+        check: |input| {
+            invariants::each_line(input, |line| {
+                if line.is_ascii() {
+                    Ok(())
+                } else {
+                    Err(format!("{line:?} is not ASCII"))
+                }
+            })
+        },
+    },
+    Invariant {
+        description: "every line contains a digit",
+        check: |input| {
+            invariants::each_line(input, |line| {
+                if line.contains(|c: char| c.is_ascii_digit()) {
+                    Ok(())
+                } else {
+                    Err(format!("{line:?} has no digits"))
+                }
+            })
+        },
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
+    file.trim().lines().map(|s| s.to_string()).collect_vec()
+}
+
 fn read_input() -> InputType {
-    fs::read_to_string("./src/bin/day01/input.txt")
-        .unwrap()
-        .trim()
-        .lines()
-        .map(|s| s.to_string())
-        .collect_vec()
+    let file = fs::read_to_string("./src/bin/day01/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
 }
 
 fn part1(input: InputType) -> Int {
@@ -66,6 +98,13 @@ fn part2(input: InputType) -> Int {
     })
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -1,3 +1,5 @@
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
 use itertools::Itertools;
 use std::fs;
 use std::time::Instant;
@@ -7,9 +9,23 @@ type Int = u32;
 // Order: RGB
 type InputType = Vec<Vec<(Int, Int, Int)>>;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day02/input.txt").unwrap();
-
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "every line is a game of red, green and blue counts",
+        check: |input| {
+            invariants::lines_match(
+                input,
+                r"^Game \d+: \d+ (red|green|blue)((, |; )\d+ (red|green|blue))*$",
+            )
+        },
+    },
+    Invariant {
+        description: "every count fits in a u32",
+        check: invariants::numbers_fit::<Int>,
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut games: InputType = Vec::with_capacity(100);
 
     for line in file.trim().lines() {
@@ -48,6 +64,14 @@ fn read_input() -> InputType {
     games
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day02/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 const R: Int = 12;
 const G: Int = 13;
 const B: Int = 14;
@@ -79,6 +103,13 @@ fn part2(input: InputType) -> Int {
         .sum()
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -3,14 +3,20 @@ use std::fs;
 use std::mem::swap;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+
 type Int = u32;
 type InputType = ([[Option<Int>; N]; N], Vec<(usize, usize, bool)>);
 
 const N: usize = 140;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day03/input.txt").unwrap();
+pub const INVARIANTS: &[Invariant] = &[Invariant {
+    description: "the schematic is 140x140",
+    check: |input| invariants::grid_size(input, N, N),
+}];
 
+pub fn parse(file: &str) -> InputType {
     let mut grid = [[None; N]; N];
     let mut symbols = Vec::new();
 
@@ -30,6 +36,14 @@ fn read_input() -> InputType {
     (grid, symbols)
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day03/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn get_adjacent_numbers(
     grid: &[[Option<Int>; N]; N],
     (y, x): (usize, usize),
@@ -160,6 +174,13 @@ fn part2((mut grid, mut symbols): InputType) -> Int {
     sum
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
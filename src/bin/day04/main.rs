@@ -1,3 +1,5 @@
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
 use itertools::Itertools;
 use std::collections::HashSet;
 use std::fs;
@@ -7,11 +9,7 @@ type Int = usize;
 type InputType = Vec<Int>;
 
 fn count_matches_for_line(line: &str) -> Int {
-    let (lhs, rhs): (&str, &str) = line
-        .split_terminator(&[':', '|'][..])
-        .skip(1)
-        .collect_tuple()
-        .unwrap();
+    let (lhs, rhs): (&str, &str) = line.split(&[':', '|'][..]).skip(1).collect_tuple().unwrap();
 
     let lhs_set = lhs.split_whitespace().collect::<HashSet<&str>>();
     let rhs_set = rhs.split_whitespace().collect::<HashSet<&str>>();
@@ -19,13 +17,21 @@ fn count_matches_for_line(line: &str) -> Int {
     lhs_set.intersection(&rhs_set).count()
 }
 
+pub const INVARIANTS: &[Invariant] = &[Invariant {
+    description: "every line is a card of winning numbers and numbers you have",
+    check: |input| invariants::lines_match(input, r"^Card +\d+:[\d ]+\|[\d ]+$"),
+}];
+
+pub fn parse(file: &str) -> InputType {
+    file.trim().lines().map(count_matches_for_line).collect()
+}
+
 fn read_input() -> InputType {
-    fs::read_to_string("./src/bin/day04/input.txt")
-        .unwrap()
-        .trim()
-        .lines()
-        .map(count_matches_for_line)
-        .collect()
+    let file = fs::read_to_string("./src/bin/day04/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
 }
 
 fn part1(input: InputType) -> Int {
@@ -50,6 +56,13 @@ fn part2(input: InputType) -> Int {
         .sum()
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -3,14 +3,53 @@ use std::fs;
 use std::mem::swap;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::generators::generator;
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::properties::{self, Property};
 use itertools::Itertools;
 
 type Int = u64;
-type InputType = (Vec<Int>, Vec<(Vec<(Int, Int)>, Vec<(Int, Int)>)>);
-
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day05/input.txt").unwrap();
-
+/// Source ranges sorted by start, with the destination range of each
+type Map = (Vec<(Int, Int)>, Vec<(Int, Int)>);
+type InputType = (Vec<Int>, Vec<Map>);
+
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the first line lists seeds as start and length pairs",
+        check: |input| {
+            let seeds = input
+                .lines()
+                .next()
+                .filter(|line| !line.is_empty())
+                .ok_or("no seeds")?;
+
+            invariants::lines_match(seeds, r"^seeds:( \d+)+$")?;
+
+            match seeds.split_whitespace().skip(1).count() {
+                n if n % 2 == 0 => Ok(()),
+                n => Err(format!("found an odd number of seeds ({n})")),
+            }
+        },
+    },
+    Invariant {
+        description: "every map is a header followed by destination, source and length lines",
+        check: |input| {
+            input.split("\n\n").skip(1).try_for_each(|chunk| {
+                let (header, ranges) = chunk.split_once('\n').unwrap_or((chunk, ""));
+
+                invariants::lines_match(header, r"^[a-z]+-to-[a-z]+ map:$")?;
+                invariants::lines_match(ranges, r"^\d+ \d+ \d+$")
+            })
+        },
+    },
+    Invariant {
+        description: "every number fits in a u32, so ranges end within a u64",
+        check: invariants::numbers_fit::<u32>,
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut chunks = file.split("\n\n");
 
     let seeds = chunks
@@ -46,6 +85,14 @@ fn read_input() -> InputType {
     (seeds, maps)
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day05/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn part1((mut seeds, maps): InputType) -> Int {
     for (srcs, dests) in maps {
         for seed in seeds.iter_mut() {
@@ -94,7 +141,7 @@ fn part2((seeds, maps): InputType) -> Int {
                 let src = &srcs[i];
 
                 // Case 1
-                if seed_to < src.0 {
+                if seed_to <= src.0 {
                     queue.push_back((seed_from, seed_to));
                     continue;
                 }
@@ -112,7 +159,7 @@ fn part2((seeds, maps): InputType) -> Int {
             let seed_dest_start = dest.0 + (seed_from - src.0);
 
             // Case 4
-            if seed_to < src.1 {
+            if seed_to <= src.1 {
                 queue.push_back((seed_dest_start, seed_dest_start + (seed_to - seed_from)));
                 continue;
             }
@@ -124,7 +171,13 @@ fn part2((seeds, maps): InputType) -> Int {
                 continue;
             }
 
-            // Case 6
+            // Case 6, splitting off any part that reaches the next source
+            if let Some(next) = srcs.get(i + 1).filter(|next| next.0 < seed_to) {
+                queue.push_back((seed_from, next.0));
+                seeds.push_back((next.0, seed_to));
+                continue;
+            }
+
             queue.push_back((seed_from, seed_to));
         }
 
@@ -134,6 +187,44 @@ fn part2((seeds, maps): InputType) -> Int {
     seeds.iter().fold(Int::MAX, |acc, (curr, _)| acc.min(*curr))
 }
 
+/// Maps a number through each map by scanning its lines, as the puzzle describes
+fn location_reference(mut n: Int, maps: &[Map]) -> Int {
+    for (srcs, dests) in maps {
+        if let Some(i) = srcs.iter().position(|src| (src.0..src.1).contains(&n)) {
+            n = n - srcs[i].0 + dests[i].0;
+        }
+    }
+
+    n
+}
+
+fn part2_reference((seeds, maps): InputType) -> Int {
+    seeds
+        .chunks(2)
+        .flat_map(|chunk| chunk[0]..chunk[0] + chunk[1])
+        .map(|seed| location_reference(seed, &maps))
+        .min()
+        .unwrap()
+}
+
+pub const PROPERTIES: &[Property] = &[Property {
+    description: "part 2 agrees with mapping every seed in every range",
+    check: |rng| {
+        let size = rng.range(1..=3) as usize;
+        let file = generator(5).unwrap()(rng, size);
+        let input = parse(&file);
+
+        properties::agree(file, part2(input.clone()), part2_reference(input))
+    },
+}];
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
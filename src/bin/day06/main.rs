@@ -1,3 +1,6 @@
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::properties::{self, Property};
 use itertools::Itertools;
 use std::fs;
 use std::time::Instant;
@@ -5,15 +8,44 @@ use std::time::Instant;
 type Int = u64;
 type InputType = (String, String);
 
-fn read_input() -> InputType {
-    fs::read_to_string("./src/bin/day06/input.txt")
-        .unwrap()
-        .lines()
+pub const INVARIANTS: &[Invariant] = &[Invariant {
+    description: "there is a line of times and a line of as many distances",
+    check: |input| {
+        let Some((times, distances)) = input.lines().collect_tuple() else {
+            return Err("expected exactly two lines".to_string());
+        };
+
+        invariants::lines_match(times, r"^Time:( +\d+)+$")?;
+        invariants::lines_match(distances, r"^Distance:( +\d+)+$")?;
+
+        let (t, d) = (
+            times.split_whitespace().count(),
+            distances.split_whitespace().count(),
+        );
+
+        if t == d {
+            Ok(())
+        } else {
+            Err(format!("found {} times but {} distances", t - 1, d - 1))
+        }
+    },
+}];
+
+pub fn parse(file: &str) -> InputType {
+    file.lines()
         .map(|s| s.split_terminator(':').nth(1).unwrap().trim().to_string())
         .collect_tuple()
         .unwrap()
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day06/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn count_ways(time: Int, distance: Int) -> Int {
     (1..time).fold(0, |ways_count, hold| {
         let travel = (time - hold) * hold;
@@ -26,6 +58,40 @@ fn count_ways(time: Int, distance: Int) -> Int {
     })
 }
 
+/// Races millisecond by millisecond, holding the button for the first `hold` of them
+fn count_ways_reference(time: Int, distance: Int) -> Int {
+    (0..=time)
+        .filter(|&hold| {
+            let mut speed = 0;
+            let mut travelled = 0;
+
+            for ms in 0..time {
+                if ms < hold {
+                    speed += 1;
+                } else {
+                    travelled += speed;
+                }
+            }
+
+            travelled > distance
+        })
+        .count() as Int
+}
+
+pub const PROPERTIES: &[Property] = &[Property {
+    description: "count_ways agrees with simulating every race",
+    check: |rng| {
+        let time = rng.range(0..=60);
+        let distance = rng.range(0..=time * time / 4 + 2);
+
+        properties::agree(
+            format!("time {time}, distance {distance}"),
+            count_ways(time, distance),
+            count_ways_reference(time, distance),
+        )
+    },
+}];
+
 fn part1((times_str, distances_str): InputType) -> Int {
     let mut ans = 1;
 
@@ -49,6 +115,13 @@ fn part2((times_str, distances_str): InputType) -> Int {
     count_ways(time, distance)
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -2,14 +2,19 @@ use std::cmp::Ordering;
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
 use itertools::Itertools;
 
 type Int = u64;
 type InputType = Vec<(String, Int)>;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day07/input.txt").unwrap();
+pub const INVARIANTS: &[Invariant] = &[Invariant {
+    description: "every line is a hand of five cards and a bid",
+    check: |input| invariants::lines_match(input, r"^[AKQJT2-9]{5} \d+$"),
+}];
 
+pub fn parse(file: &str) -> InputType {
     file.trim()
         .lines()
         .filter_map(|s| {
@@ -20,6 +25,14 @@ fn read_input() -> InputType {
         .collect_vec()
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day07/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn get_hand_strength(cards: [u32; 13], part1: bool) -> usize {
     let max_count_rest = cards[..if part1 { 13 } else { 12 }].iter().max().unwrap();
     let joker_count = if part1 { 0 } else { cards[12] };
@@ -108,6 +121,13 @@ fn part2(input: InputType) -> Int {
         .fold(0, |acc, (i, (_, bid))| acc + (i as Int + 1) * bid)
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
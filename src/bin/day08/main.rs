@@ -1,15 +1,58 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::dot::Dot;
+use advent_of_code_2023::invariants::{self, Invariant};
+use itertools::Itertools;
 use num::Integer;
 
 type Int = u64;
 type InputType = (String, HashMap<String, (String, String)>);
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day08/input.txt").unwrap();
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the first line is L/R instructions followed by a blank line",
+        check: |input| {
+            let (instructions, _) = input.split_once("\n\n").ok_or("no blank line")?;
+
+            invariants::lines_match(instructions, "^[LR]+$")
+        },
+    },
+    Invariant {
+        description: "nodes have three-character ASCII names, as they are sliced at fixed offsets",
+        check: |input| {
+            let (_, nodes) = input.split_once("\n\n").unwrap_or_default();
+
+            invariants::lines_match(nodes, r"^[0-9A-Z]{3} = \([0-9A-Z]{3}, [0-9A-Z]{3}\)$")
+        },
+    },
+    Invariant {
+        description: "AAA and ZZZ exist and every destination is a defined node",
+        check: |input| {
+            let (_, nodes) = input.split_once("\n\n").unwrap_or_default();
+
+            let defined: HashSet<_> = nodes.lines().filter_map(|l| l.get(0..3)).collect();
+
+            let referenced = nodes
+                .lines()
+                .flat_map(|l| [l.get(7..10), l.get(12..15)])
+                .flatten();
+
+            match ["AAA", "ZZZ"]
+                .into_iter()
+                .chain(referenced)
+                .find(|n| !defined.contains(n))
+            {
+                Some(node) => Err(format!("{node} is not defined")),
+                None => Ok(()),
+            }
+        },
+    },
+];
 
+pub fn parse(file: &str) -> InputType {
     let (instructions, nodes) = file.split_once("\n\n").unwrap();
 
     let mut map = HashMap::new();
@@ -25,6 +68,14 @@ fn read_input() -> InputType {
     (instructions.to_string(), map)
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day08/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn part1((instructions, nodes): InputType) -> Int {
     let mut steps = 0;
 
@@ -48,13 +99,7 @@ fn part1((instructions, nodes): InputType) -> Int {
 fn part2((instructions, nodes): InputType) -> Int {
     let mut steps_lcm = 1;
 
-    let a_nodes = nodes.iter().filter_map(|(element, _)| {
-        if element.ends_with('A') {
-            Some(element)
-        } else {
-            None
-        }
-    });
+    let a_nodes = nodes.keys().filter(|element| element.ends_with('A'));
 
     for mut curr in a_nodes {
         let mut steps = 0;
@@ -75,6 +120,37 @@ fn part2((instructions, nodes): InputType) -> Int {
     steps_lcm
 }
 
+/// Each node with an edge to its left and right neighbours, merged when they are the same.
+/// Ghosts start on the green nodes and finish on the red ones.
+pub fn dot(file: &str) -> String {
+    let (_, nodes) = parse(file);
+    let mut dot = Dot::new(&[("shape", "circle")], &[]);
+
+    for (name, (left, right)) in nodes.iter().sorted() {
+        match name.chars().last() {
+            Some('A') => dot.node(name, &[("style", "filled"), ("fillcolor", "palegreen")]),
+            Some('Z') => dot.node(name, &[("style", "filled"), ("fillcolor", "salmon")]),
+            _ => dot.node(name, &[]),
+        }
+
+        if left == right {
+            dot.edge(name, left, &[("label", "L/R")]);
+        } else {
+            dot.edge(name, left, &[("label", "L")]);
+            dot.edge(name, right, &[("label", "R"), ("style", "dashed")]);
+        }
+    }
+
+    dot.finish()
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
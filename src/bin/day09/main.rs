@@ -1,6 +1,8 @@
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
 use itertools::Itertools;
 
 type Int = i32;
@@ -8,9 +10,17 @@ type InputType = Vec<Vec<Int>>;
 
 const N: usize = 21;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day09/input.txt").unwrap();
+pub const INVARIANTS: &[Invariant] = &[Invariant {
+    description: "every line has 21 numbers",
+    check: |input| {
+        invariants::each_line(input, |line| match line.split_whitespace().count() {
+            N => Ok(()),
+            n => Err(format!("found {n} numbers")),
+        })
+    },
+}];
 
+pub fn parse(file: &str) -> InputType {
     file.trim()
         .lines()
         .map(|s| {
@@ -21,6 +31,14 @@ fn read_input() -> InputType {
         .collect_vec()
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day09/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn extrapolate(input: InputType) -> (Int, Int) {
     let mut part1 = 0;
     let mut part2 = 0;
@@ -51,6 +69,16 @@ fn extrapolate(input: InputType) -> (Int, Int) {
     (part1, part2)
 }
 
+pub const PARTS: Parts<InputType> = Parts::Combined(|input| {
+    let (part1, part2) = extrapolate(input);
+
+    (part1.into(), part2.into())
+});
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
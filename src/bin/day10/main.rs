@@ -2,6 +2,10 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::image::{Image, BLACK};
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::visual::{Animation, Colour, Grid};
 use itertools::Itertools;
 
 type Int = u16;
@@ -10,7 +14,7 @@ type Map = [[Tile; NC]; NR];
 type Coord = (usize, usize);
 
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
-enum Tile {
+pub enum Tile {
     Vert,
     Horiz,
     NE, // L
@@ -34,6 +38,20 @@ impl Tile {
         }
     }
 
+    // Box drawing characters join up where the puzzle's letters do not
+    fn symbol(&self) -> char {
+        match self {
+            Tile::Vert => '│',
+            Tile::Horiz => '─',
+            Tile::NE => '└',
+            Tile::NW => '┘',
+            Tile::SW => '┐',
+            Tile::SE => '┌',
+            Tile::Ground => '.',
+            Tile::Start => 'S',
+        }
+    }
+
     // Do the pipe on the self tile connect to the neighbour, which is to the `direction`.
     fn is_connected_to_pipe(&self, neighbour: Tile, &direction: &Direction) -> bool {
         if !neighbour.is_connected(&direction) {
@@ -55,9 +73,22 @@ impl Tile {
 const NR: usize = 140;
 const NC: usize = 140;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day10/input.txt").unwrap();
-
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the map is 140x140",
+        check: |input| invariants::grid_size(input, NR, NC),
+    },
+    Invariant {
+        description: "the map only contains pipes, ground and the start",
+        check: |input| invariants::grid_chars(input, "|-LJ7F.S"),
+    },
+    Invariant {
+        description: "there is exactly one start",
+        check: |input| invariants::count_char(input, 'S', 1),
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut map = [[Tile::Ground; NC]; NR];
     let mut start_coord = (0, 0);
 
@@ -83,6 +114,14 @@ fn read_input() -> InputType {
     (map, start_coord)
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day10/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn find_start_neighbours(map: &Map, (row, col): Coord) -> Vec<(Direction, Coord)> {
     let mut queue = Vec::<(Direction, Coord)>::new();
 
@@ -169,7 +208,7 @@ enum Direction {
 fn determine_start_tile(d1: &Direction, d2: &Direction) -> Tile {
     match (d1, d2) {
         (Direction::Up, Direction::Right) => Tile::NE,
-        (Direction::Up, Direction::Down) => Tile::Horiz,
+        (Direction::Up, Direction::Down) => Tile::Vert,
         (Direction::Up, Direction::Left) => Tile::NW,
         (Direction::Right, Direction::Down) => Tile::SE,
         (Direction::Right, Direction::Left) => Tile::Horiz,
@@ -342,7 +381,8 @@ fn traverse_and_count_adjacent(
     }
 }
 
-fn part2((mut map, (row, col)): InputType) -> Int {
+/// The tiles of the loop and the tiles it encloses
+fn trace_loop((mut map, (row, col)): InputType) -> (HashSet<Coord>, HashSet<Coord>) {
     let start_neighbours = find_start_neighbours(&map, (row, col));
 
     let (d1, d2): (_, _) = start_neighbours
@@ -372,7 +412,13 @@ fn part2((mut map, (row, col)): InputType) -> Int {
         &mut rights,
     );
 
-    count_insides(pipes, lefts, rights)
+    let insides = find_insides(&pipes, lefts, rights);
+
+    (pipes, insides)
+}
+
+fn part2(input: InputType) -> Int {
+    trace_loop(input).1.len() as Int
 }
 
 fn get_entry_direction_with_exit_direction(tile: Tile, exit_direction: &Direction) -> Direction {
@@ -391,7 +437,11 @@ fn get_entry_direction_with_exit_direction(tile: Tile, exit_direction: &Directio
     }
 }
 
-fn count_insides(pipes: HashSet<Coord>, lefts: HashSet<Coord>, rights: HashSet<Coord>) -> Int {
+fn find_insides(
+    pipes: &HashSet<Coord>,
+    lefts: HashSet<Coord>,
+    rights: HashSet<Coord>,
+) -> HashSet<Coord> {
     let mut is_left = true;
     let mut r = 0;
 
@@ -446,7 +496,82 @@ fn count_insides(pipes: HashSet<Coord>, lefts: HashSet<Coord>, rights: HashSet<C
         }
     }
 
-    insides.len() as Int
+    insides
+}
+
+/// Shows the map, then the loop in yellow, then the tiles it encloses in green.
+pub fn visualise(file: &str, animation: &mut Animation) {
+    let input = parse(file);
+    let (map, _) = input;
+    let mut grid = Grid::from_rows(map.iter().map(|row| row.iter().map(Tile::symbol)));
+
+    for (r, c) in (0..NR).cartesian_product(0..NC) {
+        grid.colour(r, c, Colour::Grey);
+    }
+
+    animation.frame("Map", &grid);
+
+    let (pipes, insides) = trace_loop(input);
+
+    for &(r, c) in &pipes {
+        grid.colour(r, c, Colour::Yellow);
+    }
+
+    animation.frame(&format!("Loop of {} tiles", pipes.len()), &grid);
+
+    grid.overlay(insides.iter().copied(), Colour::Green);
+
+    animation.finish(&format!("{} tiles enclosed", insides.len()), &grid);
+}
+
+/// Each tile as 3x3 pixels with its pipe drawn from the centre to the sides it connects, the loop
+/// in yellow over the tiles it encloses in green.
+pub fn render(file: &str) -> Image {
+    let input = parse(file);
+    let (map, start) = input;
+    let (pipes, insides) = trace_loop(input);
+    let mut image = Image::new(NC * 3, NR * 3, BLACK);
+
+    for (r, c) in (0..NR).cartesian_product(0..NC) {
+        let (background, pipe) = if (r, c) == start {
+            ([220, 40, 40], [220, 40, 40])
+        } else if pipes.contains(&(r, c)) {
+            (BLACK, [255, 204, 0])
+        } else if insides.contains(&(r, c)) {
+            ([46, 160, 67], [70, 190, 90])
+        } else {
+            (BLACK, [70, 70, 70])
+        };
+
+        let ends: &[(usize, usize)] = match map[r][c] {
+            Tile::Vert => &[(0, 1), (2, 1)],
+            Tile::Horiz => &[(1, 0), (1, 2)],
+            Tile::NE => &[(0, 1), (1, 2)],
+            Tile::NW => &[(0, 1), (1, 0)],
+            Tile::SW => &[(2, 1), (1, 0)],
+            Tile::SE => &[(2, 1), (1, 2)],
+            Tile::Ground | Tile::Start => &[],
+        };
+
+        for (y, x) in (0..3).cartesian_product(0..3) {
+            let rgb = if (y, x) == (1, 1) && !ends.is_empty() || ends.contains(&(y, x)) {
+                pipe
+            } else {
+                background
+            };
+
+            image.set(c * 3 + x, r * 3 + y, rgb);
+        }
+    }
+
+    image
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
 }
 
 pub fn main() {
@@ -3,12 +3,25 @@ use std::collections::BinaryHeap;
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::properties::{self, Property};
+
 type Int = i64;
 type InputType = (BinaryHeap<Reverse<Int>>, BinaryHeap<Reverse<Int>>);
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day11/input.txt").unwrap();
-
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the image is rectangular",
+        check: invariants::rectangular,
+    },
+    Invariant {
+        description: "the image only contains space and galaxies",
+        check: |input| invariants::grid_chars(input, ".#"),
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut rows = BinaryHeap::new();
     let mut cols = BinaryHeap::new();
 
@@ -24,6 +37,14 @@ fn read_input() -> InputType {
     (rows, cols)
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day11/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn axis_sum(mut nums: BinaryHeap<Reverse<Int>>, factor: Int) -> Int {
     let mut res = 0;
     let mut sum = 0;
@@ -50,6 +71,39 @@ fn axis_sum(mut nums: BinaryHeap<Reverse<Int>>, factor: Int) -> Int {
     res
 }
 
+/// Sums the distance along one axis between every pair of galaxies, growing each empty line
+/// between them by `factor`
+fn axis_sum_reference(nums: &[Int], factor: Int) -> Int {
+    let mut res = 0;
+
+    for (i, a) in nums.iter().enumerate() {
+        for b in &nums[i + 1..] {
+            let (from, to) = (*a.min(b), *a.max(b));
+            let empty = (from + 1..to).filter(|n| !nums.contains(n)).count() as Int;
+
+            res += to - from + empty * (factor - 1);
+        }
+    }
+
+    res
+}
+
+pub const PROPERTIES: &[Property] = &[Property {
+    description: "axis_sum agrees with summing every pair of galaxies",
+    check: |rng| {
+        let nums = (0..rng.range(0..=20))
+            .map(|_| rng.range(0..=30) as Int)
+            .collect::<Vec<_>>();
+        let factor = rng.range(1..=1000) as Int;
+
+        properties::agree(
+            format!("galaxies at {nums:?}, factor {factor}"),
+            axis_sum(nums.iter().map(|n| Reverse(*n)).collect(), factor),
+            axis_sum_reference(&nums, factor),
+        )
+    },
+}];
+
 fn sum_paths((rows, cols): InputType, factor: Int) -> Int {
     axis_sum(rows, factor) + axis_sum(cols, factor)
 }
@@ -62,6 +116,23 @@ fn part2(input: InputType) -> Int {
     sum_paths(input, 1000000)
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
+/// The example expands empty rows and columns 100 times in part 2, rather than a million times
+pub const EXAMPLE_PARTS: Parts<InputType> = Parts::Separate(
+    |input| part1(input).into(),
+    |input| sum_paths(input, 100).into(),
+);
+
+pub fn run_example(file: &str, report: &mut dyn FnMut(Answer)) {
+    EXAMPLE_PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
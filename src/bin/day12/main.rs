@@ -1,16 +1,30 @@
 use std::collections::HashMap;
 use std::fs;
-use std::iter::repeat;
+use std::iter::repeat_n;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::properties::{self, Property};
 use itertools::Itertools;
 
 type Int = u64;
 type InputType = Vec<(String, Vec<usize>)>;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day12/input.txt").unwrap();
+/// Counts already worked out for the rest of a row, whichever row it came from
+#[derive(Default)]
+struct Memo {
+    counts: HashMap<(String, Vec<usize>), Int>,
+    hits: u64,
+    misses: u64,
+}
 
+pub const INVARIANTS: &[Invariant] = &[Invariant {
+    description: "every line is a row of springs and a list of group sizes",
+    check: |input| invariants::lines_match(input, r"^[.#?]+ \d+(,\d+)*$"),
+}];
+
+pub fn parse(file: &str) -> InputType {
     let mut springs = Vec::new();
 
     for line in file.lines() {
@@ -25,12 +39,15 @@ fn read_input() -> InputType {
     springs
 }
 
-fn count_matches(
-    springs: &str,
-    groups: Vec<usize>,
-    from: usize,
-    memo: &mut HashMap<(String, Vec<usize>), Int>,
-) -> Int {
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day12/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
+fn count_matches(springs: &str, groups: Vec<usize>, from: usize, memo: &mut Memo) -> Int {
     if from >= springs.len() {
         if groups.is_empty() {
             return 1;
@@ -53,10 +70,13 @@ fn count_matches(
 
     let memo_key = (springs[from..].to_string(), groups.clone());
 
-    if let Some(ans) = memo.get(&memo_key) {
+    if let Some(ans) = memo.counts.get(&memo_key) {
+        memo.hits += 1;
         return *ans;
     }
 
+    memo.misses += 1;
+
     let mut ans = 0;
 
     // Check if any corresponding springs are '.'
@@ -72,28 +92,96 @@ fn count_matches(
         ans += count_matches(springs, groups, from + 1, memo);
     }
 
-    memo.insert(memo_key, ans);
+    memo.counts.insert(memo_key, ans);
 
     ans
 }
 
+/// Tries every way of replacing the unknown springs, keeping those with the right groups
+fn count_matches_reference(springs: &str, groups: &[usize]) -> Int {
+    let unknowns = springs.chars().filter(|c| *c == '?').count();
+
+    (0..1u32 << unknowns)
+        .filter(|mask| {
+            let mut bit = 0;
+            let arrangement = springs
+                .chars()
+                .map(|c| match c {
+                    '?' => {
+                        bit += 1;
+                        if mask >> (bit - 1) & 1 == 1 {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    }
+                    c => c,
+                })
+                .collect::<String>();
+
+            arrangement
+                .split('.')
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len())
+                .eq(groups.iter().copied())
+        })
+        .count() as Int
+}
+
+pub const PROPERTIES: &[Property] = &[Property {
+    description: "count_matches agrees with trying every arrangement",
+    check: |rng| {
+        // Groups come from a random row, some of whose springs are then hidden
+        let row = (0..rng.range(1..=14))
+            .map(|_| if rng.chance(1, 2) { '#' } else { '.' })
+            .collect::<String>();
+        let groups = row
+            .split('.')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len())
+            .collect_vec();
+        let springs = row
+            .chars()
+            .map(|c| if rng.chance(1, 2) { '?' } else { c })
+            .collect::<String>();
+
+        properties::agree(
+            format!("{springs} {}", groups.iter().join(",")),
+            count_matches(&springs, groups.clone(), 0, &mut Memo::default()),
+            count_matches_reference(&springs, &groups),
+        )
+    },
+}];
+
 fn solve(input: InputType) -> (Int, Int) {
     let mut p1 = 0;
     let mut p2 = 0;
 
-    let mut memo = HashMap::new();
+    let mut memo = Memo::default();
 
     for (springs, groups) in input {
-        let new_springs = repeat(&springs).take(5).join("?");
-        let new_groups = repeat(groups.clone()).take(5).flatten().collect_vec();
+        let new_springs = repeat_n(&springs, 5).join("?");
+        let new_groups = repeat_n(groups.clone(), 5).flatten().collect_vec();
 
         p1 += count_matches(springs.as_str(), groups, 0, &mut memo);
         p2 += count_matches(new_springs.as_str(), new_groups, 0, &mut memo);
     }
 
+    tracing::info!(memo_hits = memo.hits, memo_misses = memo.misses);
+
     (p1, p2)
 }
 
+pub const PARTS: Parts<InputType> = Parts::Combined(|input| {
+    let (part1, part2) = solve(input);
+
+    (part1.into(), part2.into())
+});
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
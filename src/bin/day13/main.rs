@@ -1,12 +1,40 @@
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+
 type Int = usize;
 type InputType = Vec<(Vec<Int>, Vec<Int>)>;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day13/input.txt").unwrap();
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "every pattern is a rectangle of ash and rocks",
+        check: |input| {
+            input.trim().split("\n\n").try_for_each(|pattern| {
+                invariants::rectangular(pattern)?;
+                invariants::grid_chars(pattern, ".#")
+            })
+        },
+    },
+    Invariant {
+        description: "patterns fit in a bitmask: at most 64 columns and 32 rows",
+        check: |input| {
+            input.trim().split("\n\n").try_for_each(|pattern| {
+                let rows = pattern.lines().count();
+                let cols = pattern.lines().map(|l| l.len()).max().unwrap_or(0);
+
+                if rows > 32 || cols > 64 {
+                    Err(format!("found a {rows}x{cols} pattern"))
+                } else {
+                    Ok(())
+                }
+            })
+        },
+    },
+];
 
+pub fn parse(file: &str) -> InputType {
     let mut patterns = Vec::new();
 
     for pattern_str in file.split("\n\n") {
@@ -36,13 +64,21 @@ fn read_input() -> InputType {
     patterns
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day13/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn differs_by_single_bit(a: Int, b: Int) -> bool {
     let xor = a ^ b;
 
     xor > 0 && ((xor & (xor - 1)) == 0)
 }
 
-fn scan(v: &Vec<Int>, part1: bool) -> Option<Int> {
+fn scan(v: &[Int], part1: bool) -> Option<Int> {
     let mut axis = 0;
     let mut delta = 1;
     let mut smudge_found = false;
@@ -119,6 +155,13 @@ fn part2(input: InputType) -> Int {
     ans
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -1,24 +1,37 @@
+use std::collections::HashSet;
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::visual::{Animation, Colour, Grid};
 use itertools::Itertools;
 
-use crate::Rock::{Cube, Round};
+use Rock::{Cube, Round};
 
 type Int = usize;
 type InputType = [[Option<Rock>; N]; N];
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash)]
-enum Rock {
+pub enum Rock {
     Round,
     Cube,
 }
 
 const N: usize = 100;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day14/input.txt").unwrap();
-
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the platform is 100x100",
+        check: |input| invariants::grid_size(input, N, N),
+    },
+    Invariant {
+        description: "the platform only contains round rocks, cube rocks and space",
+        check: |input| invariants::grid_chars(input, "O#."),
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut rocks = [[None; N]; N];
 
     for (row, line) in file.lines().enumerate() {
@@ -34,6 +47,15 @@ fn read_input() -> InputType {
     rocks
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day14/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
+#[allow(clippy::needless_range_loop)]
 fn north(input: &mut InputType) -> Int {
     let mut ans = 0;
 
@@ -59,12 +81,10 @@ fn north(input: &mut InputType) -> Int {
                     last_space += 1;
                     weight += N - r;
                 }
-                Some(Cube) => {
-                    if last_space <= r {
-                        last_space = r + 1;
-                    }
+                Some(Cube) if last_space <= r => {
+                    last_space = r + 1;
                 }
-                None => {}
+                _ => {}
             }
         }
 
@@ -74,6 +94,7 @@ fn north(input: &mut InputType) -> Int {
     ans
 }
 
+#[allow(clippy::needless_range_loop)]
 fn south(input: &mut InputType) {
     for c in 0..N {
         let mut last_space = N - 1;
@@ -93,12 +114,10 @@ fn south(input: &mut InputType) {
 
                     last_space = last_space.saturating_sub(1);
                 }
-                Some(Cube) => {
-                    if last_space >= r {
-                        last_space = r.saturating_sub(1);
-                    }
+                Some(Cube) if last_space >= r => {
+                    last_space = r.saturating_sub(1);
                 }
-                None => {}
+                _ => {}
             }
         }
     }
@@ -128,12 +147,10 @@ fn east(input: &mut InputType) -> Int {
 
                     last_space = last_space.saturating_sub(1);
                 }
-                Some(Cube) => {
-                    if last_space >= c {
-                        last_space = c.saturating_sub(1);
-                    }
+                Some(Cube) if last_space >= c => {
+                    last_space = c.saturating_sub(1);
                 }
-                None => {}
+                _ => {}
             }
         }
 
@@ -162,12 +179,10 @@ fn west(input: &mut InputType) {
 
                     last_space += 1;
                 }
-                Some(Cube) => {
-                    if last_space <= c {
-                        last_space = c + 1;
-                    }
+                Some(Cube) if last_space <= c => {
+                    last_space = c + 1;
                 }
-                None => {}
+                _ => {}
             }
         }
     }
@@ -213,6 +228,79 @@ fn part2(mut input: InputType) -> Int {
     state.2
 }
 
+fn load(input: &InputType) -> Int {
+    (0..N)
+        .map(|r| input[r].iter().filter(|rock| **rock == Some(Round)).count() * (N - r))
+        .sum()
+}
+
+fn draw(input: &InputType) -> Grid {
+    let mut grid = Grid::new(N, N);
+
+    for (r, row) in input.iter().enumerate() {
+        for (c, rock) in row.iter().enumerate() {
+            match rock {
+                Some(Round) => {
+                    grid.set(r, c, 'O');
+                    grid.colour(r, c, Colour::Yellow);
+                }
+                Some(Cube) => {
+                    grid.set(r, c, '#');
+                    grid.colour(r, c, Colour::Grey);
+                }
+                None => {}
+            }
+        }
+    }
+
+    grid
+}
+
+/// Shows the platform after each tilt of each spin cycle, until it returns to an earlier state.
+pub fn visualise(file: &str, animation: &mut Animation) {
+    type Tilt = fn(&mut InputType);
+
+    let mut input = parse(file);
+    let mut seen = HashSet::new();
+    let tilts: [(&str, Tilt); 4] = [
+        ("north", |input| {
+            north(input);
+        }),
+        ("west", west),
+        ("south", south),
+        ("east", |input| {
+            east(input);
+        }),
+    ];
+
+    animation.frame(&format!("Load {}", load(&input)), &draw(&input));
+
+    for cycle in 1.. {
+        for (direction, tilt) in tilts {
+            tilt(&mut input);
+
+            let title = format!("Cycle {cycle}, tilted {direction}, load {}", load(&input));
+            animation.frame(&title, &draw(&input));
+        }
+
+        if !seen.insert(input) {
+            let title = format!(
+                "Cycle {cycle} repeats an earlier one, load {}",
+                load(&input)
+            );
+            animation.finish(&title, &draw(&input));
+            break;
+        }
+    }
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -1,18 +1,41 @@
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
 use itertools::Itertools;
 
 type Int = usize;
 type InputType = Vec<String>;
 
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the initialization sequence is a single line",
+        check: |input| match input.trim().lines().count() {
+            1 => Ok(()),
+            n => Err(format!("found {n} lines")),
+        },
+    },
+    Invariant {
+        description: "every step is a label followed by = and a focal length, or -",
+        check: |input| {
+            let steps = input.trim().split(',').join("\n");
+
+            invariants::lines_match(&steps, "^[a-z]+(=[1-9]|-)$")
+        },
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
+    file.trim().split(',').map(|s| s.to_string()).collect_vec()
+}
+
 fn read_input() -> InputType {
-    fs::read_to_string("./src/bin/day15/input.txt")
-        .unwrap()
-        .trim()
-        .split(',')
-        .map(|s| s.to_string())
-        .collect_vec()
+    let file = fs::read_to_string("./src/bin/day15/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
 }
 
 fn get_box_n(label: &str) -> Int {
@@ -63,6 +86,13 @@ fn part2(input: InputType) -> Int {
     })
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -2,6 +2,10 @@ use std::collections::HashSet;
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::image::{self, Image};
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::visual::{Animation, Colour, Grid};
 use itertools::Itertools;
 
 type Int = usize;
@@ -9,9 +13,18 @@ type InputType = [[char; N]; N];
 
 const N: usize = 110;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day16/input.txt").unwrap();
-
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the contraption is 110x110",
+        check: |input| invariants::grid_size(input, N, N),
+    },
+    Invariant {
+        description: "the contraption only contains space, mirrors and splitters",
+        check: |input| invariants::grid_chars(input, r".|-/\"),
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut grid = [['.'; N]; N];
 
     for (i, row) in file.lines().enumerate() {
@@ -23,6 +36,14 @@ fn read_input() -> InputType {
     grid
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day16/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn get_new_rc(row: Int, col: Int, direction: Int) -> Option<(Int, Int)> {
     match direction {
         0 => row.checked_sub(1).map(|r| (r, col)),
@@ -33,6 +54,22 @@ fn get_new_rc(row: Int, col: Int, direction: Int) -> Option<(Int, Int)> {
     }
 }
 
+/// Directions a beam leaves a tile in, having entered it moving in `direction`
+fn exits(tile: char, direction: Int) -> &'static [Int] {
+    let d = match tile {
+        '.' => direction,
+        '\\' => 3 - direction,
+        '/' => (5 - direction) % 4,
+        // Beam moving horizontally into a vertical splitter, or vertically into a horizontal one
+        '|' if direction % 2 == 1 => return &[0, 2],
+        '-' if direction.is_multiple_of(2) => return &[1, 3],
+        '|' | '-' => direction,
+        _ => panic!(),
+    };
+
+    &[0, 1, 2, 3][d..=d]
+}
+
 fn step(
     grid: &InputType,
     history: &mut HashSet<(Int, Int, Int)>,
@@ -44,67 +81,10 @@ fn step(
         return;
     }
 
-    match grid[row][col] {
-        '.' => {
-            let Some((r, c)) = get_new_rc(row, col, direction) else {
-                return;
-            };
-
-            step(grid, history, r, c, direction)
-        }
-        '\\' => {
-            let d = 3 - direction;
-            let Some((r, c)) = get_new_rc(row, col, d) else {
-                return;
-            };
-
-            step(grid, history, r, c, d)
-        }
-        '/' => {
-            let d = (5 - direction) % 4;
-            let Some((r, c)) = get_new_rc(row, col, d) else {
-                return;
-            };
-
-            step(grid, history, r, c, d)
-        }
-        '|' => {
-            // Beam moving vertically
-            if direction % 2 == 0 {
-                let Some((r, c)) = get_new_rc(row, col, direction) else {
-                    return;
-                };
-
-                return step(grid, history, r, c, direction);
-            }
-
-            for d in [0, 2] {
-                let Some((r, c)) = get_new_rc(row, col, d) else {
-                    continue;
-                };
-
-                step(grid, history, r, c, d);
-            }
+    for &d in exits(grid[row][col], direction) {
+        if let Some((r, c)) = get_new_rc(row, col, d) {
+            step(grid, history, r, c, d);
         }
-        '-' => {
-            // Beam moving horizontally
-            if direction % 2 == 1 {
-                let Some((r, c)) = get_new_rc(row, col, direction) else {
-                    return;
-                };
-
-                return step(grid, history, r, c, direction);
-            }
-
-            for d in [1, 3] {
-                let Some((r, c)) = get_new_rc(row, col, d) else {
-                    continue;
-                };
-
-                step(grid, history, r, c, d);
-            }
-        }
-        _ => panic!(),
     }
 }
 
@@ -116,6 +96,7 @@ fn part1(input: InputType) -> Int {
     let mut history = HashSet::new();
 
     step(&input, &mut history, 0, 0, 1);
+    tracing::info!(beam_states = history.len());
 
     count_unique_squares(&history)
 }
@@ -150,12 +131,89 @@ fn part2(input: InputType) -> Int {
         .map(|(r, c, d)| {
             history.clear();
             step(&input, &mut history, *r, *c, *d);
+            tracing::info!(beam_states = history.len());
+
             count_unique_squares(&history)
         })
         .max()
         .unwrap()
 }
 
+/// Shows the beam from the top left advancing a tile a frame, with energised tiles highlighted.
+pub fn visualise(file: &str, animation: &mut Animation) {
+    let input = parse(file);
+    let mut grid = Grid::from_rows(input.iter().map(|row| row.iter().copied()));
+    let mut history = HashSet::new();
+    let mut energised = HashSet::new();
+    let mut beams = vec![(0, 0, 1)];
+
+    for (r, c) in (0..N).cartesian_product(0..N) {
+        if input[r][c] != '.' {
+            grid.colour(r, c, Colour::Cyan);
+        }
+    }
+
+    while !beams.is_empty() {
+        beams.retain(|beam| history.insert(*beam));
+
+        for &(r, c, _) in &beams {
+            energised.insert((r, c));
+            grid.highlight(r, c, Colour::Red);
+        }
+
+        let title = format!("{} beams, {} tiles energised", beams.len(), energised.len());
+        animation.frame(&title, &grid);
+
+        // Tiles stay highlighted once energised, with only the beams' heads in red
+        for &(r, c, _) in &beams {
+            grid.highlight(r, c, Colour::Yellow);
+        }
+
+        beams = beams
+            .iter()
+            .flat_map(|&(r, c, d)| {
+                exits(input[r][c], d)
+                    .iter()
+                    .filter_map(move |&d| get_new_rc(r, c, d).map(|(r, c)| (r, c, d)))
+            })
+            .collect();
+    }
+
+    animation.finish(&format!("{} tiles energised", energised.len()), &grid);
+}
+
+/// How many of the beams entering from the edges energise each tile, from black for none to
+/// white for the most.
+pub fn render(file: &str) -> Image {
+    let input = parse(file);
+    let mut counts = [[0; N]; N];
+    let mut history = HashSet::new();
+
+    for i in 0..N {
+        for (r, c, d) in [(0, i, 2), (i, 0, 1), (N - 1, i, 0), (i, N - 1, 3)] {
+            history.clear();
+            step(&input, &mut history, r, c, d);
+
+            for (r, c) in history.iter().map(|(r, c, _)| (*r, *c)).unique() {
+                counts[r][c] += 1;
+            }
+        }
+    }
+
+    let most = counts.iter().flatten().max().copied().unwrap_or(0).max(1);
+
+    Image::from_grid(N, N, 4, |r, c| {
+        image::heat(counts[r][c] as f64 / most as f64)
+    })
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -1,16 +1,39 @@
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
 use num::Complex;
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fs;
 use std::time::Instant;
 
 type Int = isize;
 type InputType = HashMap<Complex<Int>, Int>;
-const N: Int = 141;
-
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day17/input.txt").unwrap();
 
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the map is rectangular",
+        check: invariants::rectangular,
+    },
+    Invariant {
+        description: "the map is at least 5x5, so the ultra crucible can reach the end",
+        check: |input| {
+            let rows = input.trim().lines().count();
+            let cols = input.lines().next().map_or(0, |l| l.chars().count());
+
+            if rows >= 5 && cols >= 5 {
+                Ok(())
+            } else {
+                Err(format!("the map is {rows}x{cols}"))
+            }
+        },
+    },
+    Invariant {
+        description: "every block has a heat loss of 1 to 9",
+        check: |input| invariants::grid_chars(input, "123456789"),
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut grid = HashMap::new();
 
     file.trim().lines().enumerate().for_each(|(i, line)| {
@@ -25,12 +48,29 @@ fn read_input() -> InputType {
     grid
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day17/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn find_ans(input: InputType, min_steps: Int, max_steps: Int) -> Int {
+    let end = input.keys().fold(Complex::new(0, 0), |end, key| {
+        Complex::new(end.re.max(key.re), end.im.max(key.im))
+    });
+
     let mut queue = BinaryHeap::from([(Reverse(0), 0, 0, 0, 0)]);
     let mut seen = HashSet::new();
+    let (mut popped, mut pushed) = (0, 1);
 
     while let Some((Reverse(cost), x, y, px, py)) = queue.pop() {
-        if x == N - 1 && y == N - 1 {
+        popped += 1;
+
+        if Complex::new(x, y) == end {
+            tracing::info!(popped, pushed);
+
             return cost;
         }
 
@@ -57,7 +97,8 @@ fn find_ans(input: InputType, min_steps: Int, max_steps: Int) -> Int {
                     new_cost += d_cost;
 
                     if i >= min_steps {
-                        queue.push((Reverse(new_cost), xx, yy, dx, dy))
+                        queue.push((Reverse(new_cost), xx, yy, dx, dy));
+                        pushed += 1;
                     }
                 }
             }
@@ -75,6 +116,13 @@ fn part2(input: InputType) -> Int {
     find_ans(input, 4, 10)
 }
 
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -2,24 +2,57 @@ use std::collections::{HashMap, VecDeque};
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::image::{self, Image};
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::visual::{Animation, Colour, Grid};
+
 type Int = usize;
-type InputType = [[Int; N]; N];
-const N: Int = 141;
+type InputType = Vec<Vec<Int>>;
 type CostType = HashMap<(Int, Int, isize, isize), Int>;
 type QueueType = VecDeque<(Int, Int, isize, isize, Int)>;
 
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the map is rectangular",
+        check: invariants::rectangular,
+    },
+    Invariant {
+        description: "the map is at least 5x5, so the ultra crucible can reach the end",
+        check: |input| {
+            let rows = input.trim().lines().count();
+            let cols = input.lines().next().map_or(0, |l| l.chars().count());
+
+            if rows >= 5 && cols >= 5 {
+                Ok(())
+            } else {
+                Err(format!("the map is {rows}x{cols}"))
+            }
+        },
+    },
+    Invariant {
+        description: "every block has a heat loss of 1 to 9",
+        check: |input| invariants::grid_chars(input, "123456789"),
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
+    file.trim()
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| c.to_string().parse().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
 fn read_input() -> InputType {
     let file = fs::read_to_string("./src/bin/day17/input.txt").unwrap();
 
-    let mut grid = [[0; N]; N];
+    invariants::ensure(&file, INVARIANTS);
 
-    file.trim().lines().enumerate().for_each(|(i, line)| {
-        line.chars().enumerate().for_each(|(j, c)| {
-            grid[i][j] = c.to_string().parse().unwrap();
-        })
-    });
-
-    grid
+    parse(&file)
 }
 
 fn update_cost_and_append_to_queue(
@@ -45,9 +78,9 @@ fn update_cost_and_append_to_queue(
         });
 }
 
-fn get_answer_from_costs(costs: CostType) -> Int {
+fn get_answer_from_costs(costs: CostType, rows: Int, cols: Int) -> Int {
     costs.iter().fold(Int::MAX, |acc, (&(y, x, _, _), c)| {
-        if y == N - 1 && x == N - 1 {
+        if y == rows - 1 && x == cols - 1 {
             acc.min(*c)
         } else {
             acc
@@ -55,13 +88,18 @@ fn get_answer_from_costs(costs: CostType) -> Int {
     })
 }
 
-fn part1(input: InputType) -> Int {
+fn part1_costs(input: &InputType) -> CostType {
+    let (rows, cols) = (input.len(), input[0].len());
     let mut costs = HashMap::<(Int, Int, isize, isize), Int>::new();
     costs.insert((0, 0, 0, 0), 0);
 
     let mut queue = VecDeque::<(Int, Int, isize, isize, Int)>::from([(0, 0, 0, 0, 0)]);
+    let (mut popped, mut pushed) = (0, 1);
 
     while let Some((y, x, dy, dx, c)) = queue.pop_front() {
+        let waiting = queue.len();
+        popped += 1;
+
         if y > 0 && (-2..=0).contains(&dy) {
             let yy = y - 1;
             let new_dy = dy - 1;
@@ -70,7 +108,7 @@ fn part1(input: InputType) -> Int {
             update_cost_and_append_to_queue(&mut costs, yy, x, new_dy, 0, new_cost, &mut queue);
         }
 
-        if y < N - 1 && (0..=2).contains(&dy) {
+        if y < rows - 1 && (0..=2).contains(&dy) {
             let yy = y + 1;
             let new_dy = dy + 1;
             let new_cost = c + input[yy][x];
@@ -86,25 +124,34 @@ fn part1(input: InputType) -> Int {
             update_cost_and_append_to_queue(&mut costs, y, xx, 0, new_dx, new_cost, &mut queue);
         }
 
-        if x < N - 1 && (0..=2).contains(&dx) {
+        if x < cols - 1 && (0..=2).contains(&dx) {
             let xx = x + 1;
             let new_dx = dx + 1;
             let new_cost = c + input[y][xx];
 
             update_cost_and_append_to_queue(&mut costs, y, xx, 0, new_dx, new_cost, &mut queue);
         }
+
+        pushed += queue.len() - waiting;
     }
 
-    get_answer_from_costs(costs)
+    tracing::info!(popped, pushed, states = costs.len());
+
+    costs
 }
 
-fn part2(input: InputType) -> Int {
+fn part2_costs(input: &InputType) -> CostType {
+    let (rows, cols) = (input.len(), input[0].len());
     let mut costs = HashMap::<(Int, Int, isize, isize), Int>::new();
     costs.insert((0, 0, 0, 0), 0);
 
     let mut queue = VecDeque::<(Int, Int, isize, isize, Int)>::from([(0, 0, 0, 0, 0)]);
+    let (mut popped, mut pushed) = (0, 1);
 
     while let Some((y, x, dy, dx, c)) = queue.pop_front() {
+        let waiting = queue.len();
+        popped += 1;
+
         if dy == 0 {
             if y > 3 {
                 let yy = y - 4;
@@ -122,14 +169,14 @@ fn part2(input: InputType) -> Int {
         }
 
         if dy == 0 {
-            if y < N - 4 {
+            if y + 4 < rows {
                 let yy = y + 4;
                 let new_dy = 4;
                 let new_cost = c + (1..=4).fold(0, |acc, i| acc + input[y + i][x]);
 
                 update_cost_and_append_to_queue(&mut costs, yy, x, new_dy, 0, new_cost, &mut queue);
             }
-        } else if (0..10).contains(&dy) && y < N - 1 {
+        } else if (0..10).contains(&dy) && y < rows - 1 {
             let yy = y + 1;
             let new_dy = dy + 1;
             let new_cost = c + input[yy][x];
@@ -154,23 +201,145 @@ fn part2(input: InputType) -> Int {
         }
 
         if dx == 0 {
-            if x < N - 4 {
+            if x + 4 < cols {
                 let xx = x + 4;
                 let new_dx = 4;
                 let new_cost = c + (1..=4).fold(0, |acc, i| acc + input[y][x + i]);
 
                 update_cost_and_append_to_queue(&mut costs, y, xx, 0, new_dx, new_cost, &mut queue);
             }
-        } else if (0..10).contains(&dx) && x < N - 1 {
+        } else if (0..10).contains(&dx) && x < cols - 1 {
             let xx = x + 1;
             let new_dx = dx + 1;
             let new_cost = c + input[y][xx];
 
             update_cost_and_append_to_queue(&mut costs, y, xx, 0, new_dx, new_cost, &mut queue);
         }
+
+        pushed += queue.len() - waiting;
     }
 
-    get_answer_from_costs(costs)
+    tracing::info!(popped, pushed, states = costs.len());
+
+    costs
+}
+
+fn part1(input: InputType) -> Int {
+    get_answer_from_costs(part1_costs(&input), input.len(), input[0].len())
+}
+
+fn part2(input: InputType) -> Int {
+    get_answer_from_costs(part2_costs(&input), input.len(), input[0].len())
+}
+
+/// Walks back from the cheapest state at the bottom right to the start, through states whose
+/// costs differ by exactly the heat lost between them. After turning, the crucible moves
+/// `min_run` blocks at once.
+fn best_path(input: &InputType, costs: &CostType, min_run: isize) -> Vec<(Int, Int)> {
+    let (rows, cols) = (input.len(), input[0].len());
+    let mut state = *costs
+        .iter()
+        .filter(|(&(y, x, _, _), _)| y == rows - 1 && x == cols - 1)
+        .min_by_key(|(_, cost)| **cost)
+        .unwrap()
+        .0;
+    let mut path = vec![(state.0, state.1)];
+
+    while state != (0, 0, 0, 0) {
+        let (y, x, dy, dx) = state;
+        let (sy, sx) = (dy.signum(), dx.signum());
+        let run = dy.abs().max(dx.abs());
+        let back = if run == min_run { min_run } else { 1 };
+
+        let cells = (1..=back)
+            .map(|i| ((y as isize - sy * i) as Int, (x as isize - sx * i) as Int))
+            .collect::<Vec<_>>();
+        let lost: Int = (0..back)
+            .map(|i| input[(y as isize - sy * i) as usize][(x as isize - sx * i) as usize])
+            .sum();
+        let (py, px) = *cells.last().unwrap();
+
+        let candidates = if run == min_run {
+            // Any way of arriving moving across, or not at all at the start
+            (-10..=10)
+                .map(|n| {
+                    if sy != 0 {
+                        (py, px, 0, n)
+                    } else {
+                        (py, px, n, 0)
+                    }
+                })
+                .collect::<Vec<_>>()
+        } else {
+            vec![(py, px, dy - sy, dx - sx)]
+        };
+
+        state = candidates
+            .into_iter()
+            .find(|prev| costs.get(prev) == Some(&(costs[&state] - lost)))
+            .unwrap();
+        path.extend(cells);
+    }
+
+    path.reverse();
+    path
+}
+
+/// Draws the path each crucible takes, a block a frame, counting the heat lost along it.
+pub fn visualise(file: &str, animation: &mut Animation) {
+    let input = parse(file);
+    let digits = input.iter().map(|row| {
+        row.iter()
+            .map(|heat| char::from_digit(*heat as u32, 10).unwrap())
+    });
+
+    for (part, costs, min_run, colour) in [
+        (1, part1_costs(&input), 1, Colour::Red),
+        (2, part2_costs(&input), 4, Colour::Magenta),
+    ] {
+        let mut grid = Grid::from_rows(digits.clone());
+        let mut lost = 0;
+
+        for r in 0..grid.rows() {
+            for c in 0..grid.cols() {
+                grid.colour(r, c, Colour::Grey);
+            }
+        }
+
+        for (i, &(r, c)) in best_path(&input, &costs, min_run).iter().enumerate() {
+            if i > 0 {
+                lost += input[r][c];
+            }
+
+            grid.colour(r, c, Colour::White);
+            grid.highlight(r, c, colour);
+            animation.frame(&format!("Part {part}: {lost} heat lost"), &grid);
+        }
+
+        animation.finish(&format!("Part {part}: {lost} heat lost"), &grid);
+    }
+}
+
+/// The heat loss of each block, from dark red for 1 to pale yellow for 9, with the crucible's
+/// best path in cyan.
+pub fn render(file: &str) -> Image {
+    let input = parse(file);
+    let path = best_path(&input, &part1_costs(&input), 1);
+
+    Image::from_grid(input.len(), input[0].len(), 4, |r, c| {
+        if path.contains(&(r, c)) {
+            [0, 200, 255]
+        } else {
+            image::heat(0.15 + 0.65 * (input[r][c] - 1) as f64 / 8.0)
+        }
+    })
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
 }
 
 pub fn main() {
@@ -179,7 +348,7 @@ pub fn main() {
     let input_elapsed = now.elapsed();
 
     now = Instant::now();
-    let part1 = part1(input);
+    let part1 = part1(input.clone());
     let part1_elapsed = now.elapsed();
 
     now = Instant::now();
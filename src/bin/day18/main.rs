@@ -1,15 +1,29 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::image::{self, Image};
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::svg::{self, Svg};
 use itertools::Itertools;
 use num::Complex;
 
 type Int = i64;
 type InputType = Vec<(char, Int, String)>;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day18/input.txt").unwrap();
-
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "every line is a direction, a distance and a colour encoding a direction",
+        check: |input| invariants::lines_match(input, r"^[UDLR] \d+ \(#[0-9a-f]{5}[0-3]\)$"),
+    },
+    Invariant {
+        description: "every distance fits in an i64",
+        check: invariants::numbers_fit::<Int>,
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut plan = vec![];
 
     for line in file.lines() {
@@ -25,6 +39,14 @@ fn read_input() -> InputType {
     plan
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day18/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 fn get_delta_for_direction(dir: char) -> Complex<Int> {
     match dir {
         'R' | '0' => Complex::new(1, 0),
@@ -35,7 +57,9 @@ fn get_delta_for_direction(dir: char) -> Complex<Int> {
     }
 }
 
-fn solve(input: InputType) -> (Int, Int) {
+/// The vertices of the trench, starting and ending at the origin, and its length, as read by
+/// each part
+fn dig(input: &InputType) -> [(Vec<Complex<Int>>, Int); 2] {
     let mut curr_p1 = Complex::new(0, 0);
     let mut vertices_p1: Vec<Complex<Int>> = Vec::from([curr_p1]);
     let mut perimeter_p1 = 0;
@@ -45,7 +69,7 @@ fn solve(input: InputType) -> (Int, Int) {
     let mut perimeter_p2 = 0;
 
     for (dir, magnitude, hex) in input {
-        curr_p1 += get_delta_for_direction(dir).scale(magnitude);
+        curr_p1 += get_delta_for_direction(*dir).scale(*magnitude);
         vertices_p1.push(curr_p1);
         perimeter_p1 += magnitude;
 
@@ -57,22 +81,136 @@ fn solve(input: InputType) -> (Int, Int) {
         perimeter_p2 += magnitude;
     }
 
-    let mut p1 = 0;
-    let mut p2 = 0;
+    [(vertices_p1, perimeter_p1), (vertices_p2, perimeter_p2)]
+}
 
-    for i in 0..vertices_p1.len() - 1 {
-        p1 += (vertices_p1[i].re * vertices_p1[i + 1].im)
-            - (vertices_p1[i].im * vertices_p1[i + 1].re);
-        p2 += (vertices_p2[i].re * vertices_p2[i + 1].im)
-            - (vertices_p2[i].im * vertices_p2[i + 1].re);
-    }
+/// Shoelace area plus the half of the trench outside it, by Pick's theorem
+fn lagoon_size(vertices: &[Complex<Int>], perimeter: Int) -> Int {
+    let area: Int = vertices
+        .windows(2)
+        .map(|w| w[0].re * w[1].im - w[0].im * w[1].re)
+        .sum();
+
+    area.abs() / 2 + 1 + perimeter / 2
+}
+
+fn solve(input: InputType) -> (Int, Int) {
+    let [(vertices_p1, perimeter_p1), (vertices_p2, perimeter_p2)] = dig(&input);
 
     (
-        p1.abs() / 2 + 1 + perimeter_p1 / 2,
-        p2.abs() / 2 + 1 + perimeter_p2 / 2,
+        lagoon_size(&vertices_p1, perimeter_p1),
+        lagoon_size(&vertices_p2, perimeter_p2),
     )
 }
 
+/// Both parts' trenches side by side, each scaled to fit a panel. Part 1's edges are drawn in the
+/// colours of the instructions that dug them.
+pub fn svg(file: &str) -> String {
+    const PANEL: f64 = 600.0;
+    const MARGIN: f64 = 40.0;
+
+    let input = parse(file);
+    let mut svg = Svg::new(2.0 * (PANEL + 2.0 * MARGIN), PANEL + 3.0 * MARGIN);
+
+    svg.rect(
+        (0.0, 0.0),
+        (2.0 * (PANEL + 2.0 * MARGIN), PANEL + 3.0 * MARGIN),
+        "#111",
+    );
+
+    for (part, (vertices, perimeter)) in (1..).zip(dig(&input)) {
+        let left = (part - 1) as f64 * (PANEL + 2.0 * MARGIN) + MARGIN;
+        let points = vertices
+            .iter()
+            .map(|v| (v.re as f64, v.im as f64))
+            .collect_vec();
+        let points = svg::fit(&points, (left, 2.0 * MARGIN), PANEL);
+
+        svg.text(
+            (left, MARGIN),
+            16.0,
+            "#eee",
+            &format!(
+                "Part {part}: perimeter {perimeter}, area {}",
+                lagoon_size(&vertices, perimeter)
+            ),
+        );
+
+        if part == 1 {
+            svg.polygon(&points, "none", "#333", 0.0);
+
+            for (edge, (_, _, hex)) in points.windows(2).zip(&input) {
+                svg.line(edge[0], edge[1], &format!("#{hex}"), 2.0);
+            }
+        } else {
+            svg.polygon(&points, "#4af", "#333", 1.5);
+        }
+    }
+
+    svg.finish()
+}
+
+/// The part 1 lagoon, 2x2 pixels a cubic metre, with each trench cell in the colour of the
+/// instruction that dug it and the interior in grey.
+pub fn render(file: &str) -> Image {
+    let mut trench = HashMap::new();
+    let mut curr = Complex::new(0, 0);
+
+    for (dir, magnitude, hex) in parse(file) {
+        for _ in 0..magnitude {
+            curr += get_delta_for_direction(dir);
+            trench.insert((-curr.im, curr.re), image::hex(&hex).unwrap());
+        }
+    }
+
+    let (Some((top, bottom)), Some((left, right))) = (
+        trench.keys().map(|k| k.0).minmax().into_option(),
+        trench.keys().map(|k| k.1).minmax().into_option(),
+    ) else {
+        // Every instruction digs nothing, so there is no lagoon to draw
+        return Image::new(0, 0, image::BLACK);
+    };
+
+    // A border of one cell round the trench, so the outside is connected
+    let (top, left) = (top - 1, left - 1);
+    let (height, width) = ((bottom - top + 2) as usize, (right - left + 2) as usize);
+
+    let mut outside = HashSet::from([(top, left)]);
+    let mut queue = vec![(top, left)];
+
+    while let Some((r, c)) = queue.pop() {
+        for (rr, cc) in [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)] {
+            if (top..top + height as Int).contains(&rr)
+                && (left..left + width as Int).contains(&cc)
+                && !trench.contains_key(&(rr, cc))
+                && outside.insert((rr, cc))
+            {
+                queue.push((rr, cc));
+            }
+        }
+    }
+
+    Image::from_grid(height, width, 2, |r, c| {
+        let cell = (top + r as Int, left + c as Int);
+
+        match trench.get(&cell) {
+            Some(rgb) => *rgb,
+            None if outside.contains(&cell) => image::BLACK,
+            None => [60, 60, 60],
+        }
+    })
+}
+
+pub const PARTS: Parts<InputType> = Parts::Combined(|input| {
+    let (part1, part2) = solve(input);
+
+    (part1.into(), part2.into())
+});
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
@@ -1,125 +1,578 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::fs;
-use std::time::Instant;
-
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::dot::Dot;
+use advent_of_code_2023::generators;
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::properties::{self, Property};
 use itertools::Itertools;
+use regex::Regex;
 
 type Int = u64;
-type Workflows = HashMap<String, Vec<Rule>>;
-type Part = [Int; 4];
+/// Ratings in the order of [`Workflows::categories`]
+type Part = Vec<Int>;
 type InputType = (Workflows, Vec<Part>);
+/// A box of parts, with ratings for each category from the start of its range up to the end
+pub type Ranges = Vec<Range<Int>>;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Operator {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+}
 
-struct Rule {
-    category: usize,
-    operator: char,
-    value: Int,
+impl Operator {
+    const SYMBOLS: [(&'static str, Operator); 6] = [
+        ("<=", Operator::LessOrEqual),
+        (">=", Operator::GreaterOrEqual),
+        ("==", Operator::Equal),
+        ("!=", Operator::NotEqual),
+        ("<", Operator::Less),
+        (">", Operator::Greater),
+    ];
+
+    /// Splits a condition such as `x<=10` into its category, operator and value
+    fn split(condition: &str) -> Option<(&str, Operator, &str)> {
+        Operator::SYMBOLS.iter().find_map(|(symbol, operator)| {
+            let (category, value) = condition.split_once(symbol)?;
+
+            Some((category, *operator, value))
+        })
+    }
+
+    fn holds(self, rating: Int, value: Int) -> bool {
+        match self {
+            Operator::Less => rating < value,
+            Operator::LessOrEqual => rating <= value,
+            Operator::Greater => rating > value,
+            Operator::GreaterOrEqual => rating >= value,
+            Operator::Equal => rating == value,
+            Operator::NotEqual => rating != value,
+        }
+    }
+
+    /// The parts of `range` where a rating compared with `value` holds, and where it doesn't,
+    /// leaving out empty ones
+    fn split_range(self, range: &Range<Int>, value: Int) -> (Vec<Range<Int>>, Vec<Range<Int>>) {
+        let at = |v: Int| v.clamp(range.start, range.end);
+        let (below, after) = (at(value), at(value.saturating_add(1)));
+
+        // Either side is at most two ranges, `none` filling in where it's only one
+        let none = 0..0;
+        let (holds, fails) = match self {
+            Operator::Less => ([range.start..below, none.clone()], [below..range.end, none]),
+            Operator::LessOrEqual => ([range.start..after, none.clone()], [after..range.end, none]),
+            Operator::Greater => ([after..range.end, none.clone()], [range.start..after, none]),
+            Operator::GreaterOrEqual => {
+                ([below..range.end, none.clone()], [range.start..below, none])
+            }
+            Operator::Equal => ([below..after, none], [range.start..below, after..range.end]),
+            Operator::NotEqual => ([range.start..below, after..range.end], [below..after, none]),
+        };
+
+        let non_empty =
+            |ranges: [Range<Int>; 2]| ranges.into_iter().filter(|r| !r.is_empty()).collect();
+
+        (non_empty(holds), non_empty(fails))
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (symbol, _) = Operator::SYMBOLS.iter().find(|(_, op)| op == self).unwrap();
+
+        write!(f, "{symbol}")
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Condition {
+    /// Compares the rating of the category with this index against a value
+    Compare {
+        category: usize,
+        operator: Operator,
+        value: Int,
+    },
+    /// The fallback at the end of a workflow, which every part matches
+    Otherwise,
+}
+
+#[derive(Clone, Debug)]
+pub struct Rule {
+    condition: Condition,
     destination: String,
 }
 
 impl Rule {
-    fn otherwise(destination: String) -> Rule {
-        Rule {
-            category: 0,
-            operator: '<',
-            value: Int::MAX,
-            destination,
+    fn matches(&self, part: &Part) -> bool {
+        match self.condition {
+            Condition::Compare {
+                category,
+                operator,
+                value,
+            } => operator.holds(part[category], value),
+            Condition::Otherwise => true,
         }
     }
 }
 
-fn category_to_idx(category: &char) -> usize {
-    match category {
-        'x' => 0,
-        'm' => 1,
-        'a' => 2,
-        's' => 3,
-        _ => panic!(),
+/// The workflows by name, with the categories parts are rated in, which rules refer to by index
+#[derive(Clone, Debug, Default)]
+pub struct Workflows {
+    pub categories: Vec<String>,
+    pub rules: HashMap<String, Vec<Rule>>,
+}
+
+impl Workflows {
+    /// Every part the puzzle asks about, rating each category from 1 to 4000
+    pub fn all(&self) -> Ranges {
+        vec![1..4001; self.categories.len()]
+    }
+
+    fn describe(&self, condition: &Condition) -> String {
+        match condition {
+            Condition::Compare {
+                category,
+                operator,
+                value,
+            } => format!("{}{operator}{value}", self.categories[*category]),
+            Condition::Otherwise => "otherwise".to_string(),
+        }
     }
 }
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day19/input.txt").unwrap();
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "workflows and parts are separated by a blank line",
+        check: |input| {
+            input
+                .split_once("\n\n")
+                .map(|_| ())
+                .ok_or("no blank line".into())
+        },
+    },
+    Invariant {
+        description:
+            "workflow rules compare a category using <, >, <=, >=, == or != and end with a fallback",
+        check: |input| {
+            let (workflows, _) = input.split_once("\n\n").unwrap_or_default();
+
+            invariants::lines_match(
+                workflows,
+                r"^[a-z]+\{([a-z]+(<=|>=|==|!=|<|>)\d+:([a-z]+|A|R),)*([a-z]+|A|R)\}$",
+            )
+        },
+    },
+    Invariant {
+        description: "every part rates the same categories in the same order, each once",
+        check: |input| {
+            let (_, parts) = input.split_once("\n\n").unwrap_or_default();
+
+            invariants::lines_match(parts, r"^\{[a-z]+=\d+(,[a-z]+=\d+)*\}$")?;
+
+            let categories = |line: &str| {
+                line.trim_matches(&['{', '}'][..])
+                    .split(',')
+                    .map(|rating| rating.split('=').next().unwrap().to_string())
+                    .collect_vec()
+            };
+            let first = parts.lines().next().map(categories).unwrap_or_default();
+
+            if !first.iter().all_unique() {
+                return Err(format!(
+                    "a category is rated twice in {{{}}}",
+                    first.join(",")
+                ));
+            }
+
+            match parts.lines().find(|line| categories(line) != first) {
+                Some(line) => Err(format!(
+                    "{line} rates different categories to the first part"
+                )),
+                None => Ok(()),
+            }
+        },
+    },
+    Invariant {
+        description: "the parts rate every category the rules compare",
+        check: |input| {
+            let (workflows, parts) = input.split_once("\n\n").unwrap_or_default();
+
+            let Some(first) = parts.lines().next() else {
+                return Ok(());
+            };
+
+            let rated = Regex::new(r"([a-z]+)=").unwrap();
+            let rated: HashSet<_> = rated
+                .captures_iter(first)
+                .map(|c| c.get(1).unwrap().as_str())
+                .collect();
+            let compared = Regex::new(r"[{,]([a-z]+)(<|>|=|!)").unwrap();
+            let missing = compared
+                .captures_iter(workflows)
+                .map(|c| c.get(1).unwrap().as_str())
+                .find(|category| !rated.contains(category));
+
+            match missing {
+                Some(category) => Err(format!("no part rates {category}")),
+                None => Ok(()),
+            }
+        },
+    },
+    Invariant {
+        description: "the workflow `in` exists and every destination is a defined workflow",
+        check: |input| {
+            let (workflows, _) = input.split_once("\n\n").unwrap_or_default();
+
+            let defined: HashSet<_> = workflows
+                .lines()
+                .filter_map(|l| l.split('{').next())
+                .collect();
+
+            let destinations = workflows
+                .lines()
+                .flat_map(|l| l.trim_end_matches('}').split(&['{', ','][..]).skip(1))
+                .map(|rule| rule.rsplit(':').next().unwrap())
+                .filter(|d| *d != "A" && *d != "R");
+
+            match std::iter::once("in")
+                .chain(destinations)
+                .find(|d| !defined.contains(d))
+            {
+                Some(name) => Err(format!("workflow {name} is not defined")),
+                None => Ok(()),
+            }
+        },
+    },
+    Invariant {
+        description: "every rating and threshold fits in a u64",
+        check: invariants::numbers_fit::<Int>,
+    },
+    Invariant {
+        description: "every rating is from 1 to 4000, as validate assumes when looking for loops",
+        check: |input| {
+            let (_, parts) = input.split_once("\n\n").unwrap_or_default();
+
+            let ratings = Regex::new(r"=(\d+)").unwrap();
+            let outside = ratings
+                .captures_iter(parts)
+                .map(|c| c.get(1).unwrap().as_str())
+                .find(|rating| !rating.parse().is_ok_and(|r: Int| (1..=4000).contains(&r)));
+
+            match outside {
+                Some(rating) => Err(format!("found a rating of {rating}")),
+                None => Ok(()),
+            }
+        },
+    },
+    Invariant {
+        description: "no part can loop between workflows forever",
+        check: |input| {
+            // Input the other invariants reject can't be parsed to look for loops
+            if invariants::check(input, &INVARIANTS[..INVARIANTS.len() - 1]).is_err() {
+                return Ok(());
+            }
+
+            match validate(&parse(input).0)
+                .into_iter()
+                .find(|problem| matches!(problem, Problem::Cycle(_)))
+            {
+                Some(cycle) => Err(cycle.to_string()),
+                None => Ok(()),
+            }
+        },
+    },
+];
 
+pub fn parse(file: &str) -> InputType {
     let (workflows_str, parts_str) = file.split_once("\n\n").unwrap();
 
-    let mut workflows = HashMap::new();
+    let mut workflows = Workflows::default();
     let mut parts = Vec::new();
 
+    let ratings = parts_str
+        .lines()
+        .map(|line| {
+            line[1..line.len() - 1]
+                .split(',')
+                .map(|rating| rating.split_once('=').unwrap())
+                .collect_vec()
+        })
+        .collect_vec();
+
+    // The parts decide the order of the categories, followed by any only the rules compare
+    if let Some(first) = ratings.first() {
+        workflows.categories = first.iter().map(|(name, _)| name.to_string()).collect();
+    }
+
     for line in workflows_str.lines() {
         let (name, rules_str) = line.split_once('{').unwrap();
 
         let rules = rules_str[..rules_str.len() - 1]
             .split(',')
             .filter_map(|rule_str| {
-                if let Some((condition, destination)) = rule_str.split_once(':') {
-                    let mut condition = condition.chars();
-
-                    Some(Rule {
-                        category: category_to_idx(&condition.next()?),
-                        operator: condition.next()?,
-                        value: condition.join("").parse::<Int>().ok()?,
-                        destination: destination.to_string(),
-                    })
-                } else {
-                    Some(Rule::otherwise(rule_str.to_string()))
-                }
+                let Some((condition, destination)) = rule_str.split_once(':') else {
+                    return Some(Rule {
+                        condition: Condition::Otherwise,
+                        destination: rule_str.to_string(),
+                    });
+                };
+
+                let (category, operator, value) = Operator::split(condition)?;
+                let category = match workflows.categories.iter().position(|c| c == category) {
+                    Some(i) => i,
+                    None => {
+                        workflows.categories.push(category.to_string());
+                        workflows.categories.len() - 1
+                    }
+                };
+
+                Some(Rule {
+                    condition: Condition::Compare {
+                        category,
+                        operator,
+                        value: value.parse::<Int>().ok()?,
+                    },
+                    destination: destination.to_string(),
+                })
             })
             .collect_vec();
 
-        workflows.insert(name.to_string(), rules);
+        workflows.rules.insert(name.to_string(), rules);
     }
 
-    for line in parts_str.lines() {
-        let mut numbers = [0; 4];
+    for line in ratings {
+        parts.push(
+            line.iter()
+                .map(|(_, n)| n.parse::<Int>().unwrap())
+                .collect(),
+        );
+    }
 
-        for (i, s) in line[1..line.len() - 1].split(',').enumerate() {
-            numbers[i] = s[2..].parse::<Int>().unwrap();
-        }
+    (workflows, parts)
+}
+
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day19/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
 
-        parts.push(numbers);
+    parse(&file)
+}
+
+/// The way a part went through the workflows and whether it was accepted at the end
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trace {
+    pub accepted: bool,
+    /// Each workflow the part went through and the rule, counting from 0, that it matched there
+    pub steps: Vec<(String, usize)>,
+}
+
+/// Follows a part from `in` until it is accepted or rejected. The workflows must be free of
+/// cycles and dangling destinations, as `validate` checks.
+pub fn trace(workflows: &Workflows, part: &Part) -> Trace {
+    let mut steps = vec![];
+    let mut curr_workflow = "in";
+
+    while curr_workflow != "A" && curr_workflow != "R" {
+        let (i, rule) = workflows.rules[curr_workflow]
+            .iter()
+            .find_position(|rule| rule.matches(part))
+            .unwrap();
+
+        steps.push((curr_workflow.to_string(), i));
+        curr_workflow = &rule.destination;
     }
 
-    (workflows, parts)
+    Trace {
+        accepted: curr_workflow == "A",
+        steps,
+    }
 }
 
-fn part1((workflows, parts): &InputType) -> Int {
-    let mut ans = 0;
-    for part in parts {
-        let mut curr_workflow = "in".to_string();
+/// Where a part goes after a test in a [`DecisionTree`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Jump {
+    Test(usize),
+    Accept,
+    Reject,
+    /// Round workflows that are only a fallback forever, which the invariants keep parts from
+    Loop,
+}
 
-        while &curr_workflow != "A" && &curr_workflow != "R" {
-            for Rule {
-                category,
-                operator,
-                value,
-                destination,
-            } in workflows.get(&curr_workflow).unwrap()
-            {
-                if (*operator == '<' && part[*category] < *value)
-                    || (*operator == '>' && part[*category] > *value)
-                {
-                    curr_workflow = destination.clone();
-                    break;
+/// A compared rule, sending parts that match it to `then` and the rest to `otherwise`
+#[derive(Copy, Clone, Debug)]
+struct Test {
+    category: usize,
+    operator: Operator,
+    value: Int,
+    then: Jump,
+    otherwise: Jump,
+}
+
+/// The workflows compiled to tests that jump to each other by index, so sorting a part looks up
+/// no names. Each workflow's tests sit one after another, and workflows that are only a fallback
+/// are jumped over.
+#[derive(Clone, Debug)]
+pub struct DecisionTree {
+    tests: Vec<Test>,
+    start: Jump,
+}
+
+impl DecisionTree {
+    /// Compiles the workflows, which must end with their only fallback and have no dangling
+    /// destinations, as the invariants check. Cycles compile too, and only parts reaching them go
+    /// round, as when tracing.
+    pub fn compile(workflows: &Workflows) -> DecisionTree {
+        // Numbered in name order, so the layout doesn't depend on the map's
+        let sorted = workflows
+            .rules
+            .iter()
+            .sorted_by_key(|(name, _)| name.as_str())
+            .collect_vec();
+
+        let mut first = HashMap::new();
+        let mut len = 0;
+
+        for (name, rules) in &sorted {
+            first.insert(name.as_str(), (len, rules.len() - 1));
+            len += rules.len() - 1;
+        }
+
+        let jump = |mut destination| {
+            for _ in 0..=workflows.rules.len() {
+                match destination {
+                    "A" => return Jump::Accept,
+                    "R" => return Jump::Reject,
+                    _ => match first[destination] {
+                        (_, 0) => {
+                            destination = workflows.rules[destination][0].destination.as_str()
+                        }
+                        (i, _) => return Jump::Test(i),
+                    },
                 }
             }
+
+            Jump::Loop
+        };
+
+        let mut tests = Vec::with_capacity(len);
+
+        for (_, rules) in sorted {
+            let (fallback, compared) = rules.split_last().unwrap();
+
+            for (i, rule) in compared.iter().enumerate() {
+                let Condition::Compare {
+                    category,
+                    operator,
+                    value,
+                } = rule.condition
+                else {
+                    panic!("Only the last rule of a workflow can be a fallback");
+                };
+
+                tests.push(Test {
+                    category,
+                    operator,
+                    value,
+                    then: jump(rule.destination.as_str()),
+                    otherwise: if i + 1 < compared.len() {
+                        Jump::Test(tests.len() + 1)
+                    } else {
+                        jump(fallback.destination.as_str())
+                    },
+                });
+            }
         }
 
-        if curr_workflow == "A" {
-            ans += part.iter().sum::<Int>();
+        DecisionTree {
+            tests,
+            start: jump("in"),
+        }
+    }
+
+    pub fn accepts(&self, part: &[Int]) -> bool {
+        let mut jump = self.start;
+
+        loop {
+            match jump {
+                Jump::Test(i) => {
+                    let test = &self.tests[i];
+
+                    jump = if test.operator.holds(part[test.category], test.value) {
+                        test.then
+                    } else {
+                        test.otherwise
+                    };
+                }
+                Jump::Accept => return true,
+                Jump::Reject => return false,
+                Jump::Loop => panic!("Part {part:?} goes round workflows forever"),
+            }
         }
     }
+}
 
-    ans
+fn part1((workflows, parts): &InputType) -> Int {
+    let tree = DecisionTree::compile(workflows);
+
+    parts
+        .iter()
+        .filter(|part| tree.accepts(part))
+        .map(|part| part.iter().sum::<Int>())
+        .sum()
 }
 
-fn part2((workflows, _): InputType) -> Int {
-    let mut ans = 0;
+/// Splits the ratings reaching a rule into boxes matching it and the rest, which go on to the
+/// next rule, leaving out empty ones. `==` leaves the rest in two boxes, as `!=` does the matches.
+fn split(rule: &Rule, ranges: &Ranges) -> (Vec<Ranges>, Vec<Ranges>) {
+    let Condition::Compare {
+        category,
+        operator,
+        value,
+    } = rule.condition
+    else {
+        return (vec![ranges.clone()], vec![]);
+    };
+
+    let (matching, rest) = operator.split_range(&ranges[category], value);
+
+    let with = |pieces: Vec<Range<Int>>| {
+        pieces
+            .into_iter()
+            .map(|piece| {
+                let mut ranges = ranges.clone();
+                ranges[category] = piece;
+                ranges
+            })
+            .collect()
+    };
+
+    (with(matching), with(rest))
+}
 
-    let mut queue = VecDeque::from([("in".to_string(), [1..4001, 1..4001, 1..4001, 1..4001])]);
+/// Splits `bounds` into the boxes of parts the workflows accept, which don't overlap, by sending
+/// ranges of ratings through the workflows and splitting them at each rule.
+pub fn accepted_regions(workflows: &Workflows, bounds: &Ranges) -> Vec<Ranges> {
+    let mut accepted = vec![];
+
+    let mut queue = VecDeque::from([("in".to_string(), bounds.clone())]);
+
+    while let Some((workflow, ranges)) = queue.pop_front() {
+        if ranges.iter().any(|r| r.is_empty()) {
+            continue;
+        }
 
-    while let Some((workflow, mut ranges)) = queue.pop_front() {
         if &workflow == "A" {
-            ans += ranges.iter().map(|r| r.end - r.start).product::<Int>();
+            accepted.push(ranges);
             continue;
         }
 
@@ -127,46 +580,354 @@ fn part2((workflows, _): InputType) -> Int {
             continue;
         }
 
-        for Rule {
-            category,
-            operator,
-            value,
-            destination,
-        } in workflows.get(&workflow).unwrap()
-        {
-            let curr_range = ranges[*category].clone();
+        let mut rest = vec![ranges];
 
-            if curr_range.contains(value) {
-                let mut matching_arr = ranges.clone();
+        for rule in workflows.rules.get(&workflow).unwrap() {
+            let mut remaining = vec![];
 
-                if *operator == '<' {
-                    matching_arr[*category] = curr_range.start..*value;
+            for ranges in rest {
+                let (matching, unmatched) = split(rule, &ranges);
 
-                    queue.push_back((destination.clone(), matching_arr));
+                queue.extend(matching.into_iter().map(|m| (rule.destination.clone(), m)));
+                remaining.extend(unmatched);
+            }
 
-                    ranges[*category] = *value..curr_range.end;
+            rest = remaining;
+        }
+    }
 
-                    continue;
-                }
+    accepted
+}
 
-                matching_arr[*category] = (*value + 1)..curr_range.end;
+/// How many of the parts in `region` the workflows accept
+pub fn count_accepted(workflows: &Workflows, region: &Ranges) -> Int {
+    accepted_regions(workflows, region)
+        .iter()
+        .map(|ranges| ranges.iter().map(|r| r.end - r.start).product::<Int>())
+        .sum()
+}
 
-                queue.push_back((destination.clone(), matching_arr));
+/// Counts accepted parts in a small random box one at a time by tracing each
+fn count_accepted_reference(workflows: &Workflows, region: &Ranges) -> Int {
+    region
+        .iter()
+        .cloned()
+        .multi_cartesian_product()
+        .filter(|ratings| trace(workflows, ratings).accepted)
+        .count() as Int
+}
 
-                ranges[*category] = curr_range.start..(*value + 1);
+pub const PROPERTIES: &[Property] = &[
+    Property {
+        description: "count_accepted agrees with tracing every part in a region",
+        check: |rng| {
+            let input = generators::generator(19).unwrap()(rng, 10);
+            let (workflows, _) = parse(&input);
+            let values = workflows
+                .rules
+                .values()
+                .flatten()
+                .filter_map(|rule| match rule.condition {
+                    Condition::Compare { value, .. } => Some(value),
+                    Condition::Otherwise => None,
+                })
+                .collect_vec();
+
+            // Boxes around the rules' values, half the time, to catch where they split ranges
+            let region: Ranges = (0..workflows.categories.len())
+                .map(|_| {
+                    let start = if values.is_empty() || rng.chance(1, 2) {
+                        rng.range(1..=4000)
+                    } else {
+                        rng.choose(&values).saturating_sub(rng.range(0..=4)).max(1)
+                    };
+
+                    start..(start + rng.range(0..=8)).min(4001)
+                })
+                .collect();
+
+            properties::agree(
+                format!("{region:?}"),
+                count_accepted(&workflows, &region),
+                count_accepted_reference(&workflows, &region),
+            )
+        },
+    },
+    Property {
+        description: "the decision tree accepts the parts tracing accepts",
+        check: |rng| {
+            let input = generators::generator(19).unwrap()(rng, 20);
+            let (workflows, parts) = parse(&input);
+            let tree = DecisionTree::compile(&workflows);
+
+            parts.iter().try_for_each(|part| {
+                properties::agree(
+                    format!("{part:?}"),
+                    tree.accepts(part),
+                    trace(&workflows, part).accepted,
+                )
+            })
+        },
+    },
+];
+
+/// Times sorting the parts by tracing them through the workflows against compiling the
+/// workflows and running the parts down the decision tree, which must accept the same parts.
+pub fn bench(file: &str) -> Result<Vec<(&'static str, Duration)>, String> {
+    let (workflows, parts) = parse(file);
+
+    let now = Instant::now();
+    let traced = parts
+        .iter()
+        .map(|part| trace(&workflows, part).accepted)
+        .collect_vec();
+    let tracing = now.elapsed();
+
+    let now = Instant::now();
+    let tree = DecisionTree::compile(&workflows);
+    let compiling = now.elapsed();
+
+    let now = Instant::now();
+    let compiled = parts.iter().map(|part| tree.accepts(part)).collect_vec();
+    let sorting = now.elapsed();
+
+    if let Some(i) = (0..parts.len()).find(|&i| traced[i] != compiled[i]) {
+        return Err(format!(
+            "tracing {} part {:?} but the decision tree {} it",
+            if traced[i] { "accepts" } else { "rejects" },
+            parts[i],
+            if compiled[i] { "accepts" } else { "rejects" },
+        ));
+    }
 
-                continue;
+    Ok(vec![
+        ("trace", tracing),
+        ("compile", compiling),
+        ("decision tree", sorting),
+    ])
+}
+
+fn part2((workflows, _): InputType) -> Int {
+    count_accepted(&workflows, &workflows.all())
+}
+
+/// Something about the workflows that would hang or crash the solution, or is left over
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Problem {
+    /// Workflows some parts go round forever, starting and ending with the same one
+    Cycle(Vec<String>),
+    /// A rule sends parts to a workflow that doesn't exist
+    Dangling {
+        workflow: String,
+        destination: String,
+    },
+    /// There is no `in` workflow for parts to start at
+    NoStart,
+    /// No part ever gets to the workflow
+    Unreachable(String),
+    /// The rule, counting from 0, matches none of the parts that get to it
+    DeadRule { workflow: String, rule: usize },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Cycle(names) => write!(f, "parts can loop through {}", names.join(" -> ")),
+            Problem::Dangling {
+                workflow,
+                destination,
+            } => write!(
+                f,
+                "{workflow} sends parts to {destination}, which is not a workflow"
+            ),
+            Problem::NoStart => write!(f, "there is no workflow named in"),
+            Problem::Unreachable(name) => write!(f, "no part gets to {name}"),
+            Problem::DeadRule { workflow, rule } => write!(
+                f,
+                "rule {} of {workflow} matches none of the parts that get to it",
+                rule + 1
+            ),
+        }
+    }
+}
+
+/// Where parts that make it through the workflows can go
+#[derive(Default)]
+struct Reach<'a> {
+    workflows: HashSet<&'a str>,
+    rules: HashSet<(&'a str, usize)>,
+    cycles: HashSet<Vec<&'a str>>,
+}
+
+/// Follows the ratings that reach `name` through its rules, as `part2` does, but depth first to
+/// keep the path that led there and spot parts coming back round to a workflow on it.
+fn explore<'a>(
+    workflows: &'a Workflows,
+    name: &'a str,
+    ranges: Ranges,
+    path: &mut Vec<&'a str>,
+    reach: &mut Reach<'a>,
+) {
+    let Some((name, rules)) = workflows.rules.get_key_value(name) else {
+        return;
+    };
+
+    reach.workflows.insert(name);
+    path.push(name);
+
+    let mut rest = vec![ranges];
+
+    for (i, rule) in rules.iter().enumerate() {
+        let mut remaining = vec![];
+
+        for ranges in rest {
+            let (matching, unmatched) = split(rule, &ranges);
+
+            for matching in matching {
+                reach.rules.insert((name, i));
+
+                if let Some(start) = path.iter().position(|n| *n == rule.destination) {
+                    // The same cycle is found from each workflow on it, so start it at the first
+                    // name
+                    let mut cycle = path[start..].to_vec();
+                    let first = cycle.iter().position_min().unwrap();
+
+                    cycle.rotate_left(first);
+                    cycle.push(cycle[0]);
+                    reach.cycles.insert(cycle);
+                } else {
+                    explore(workflows, &rule.destination, matching, path, reach);
+                }
             }
 
-            if (*operator == '<' && curr_range.end <= *value)
-                || (*operator == '>' && curr_range.start >= *value)
+            remaining.extend(unmatched);
+        }
+
+        rest = remaining;
+    }
+
+    path.pop();
+}
+
+/// Checks the workflows for rules sending parts to missing workflows and for parts that could go
+/// round forever, which `part1` can't cope with, and for workflows and rules no part ever gets to.
+pub fn validate(workflows: &Workflows) -> Vec<Problem> {
+    let mut problems = vec![];
+
+    for (name, rules) in workflows.rules.iter().sorted_by_key(|(name, _)| *name) {
+        for rule in rules {
+            let destination = &rule.destination;
+
+            if destination != "A"
+                && destination != "R"
+                && !workflows.rules.contains_key(destination)
             {
-                queue.push_back((destination.clone(), ranges.clone()));
+                problems.push(Problem::Dangling {
+                    workflow: name.clone(),
+                    destination: destination.clone(),
+                });
             }
         }
     }
 
-    ans
+    if !workflows.rules.contains_key("in") {
+        problems.push(Problem::NoStart);
+        return problems;
+    }
+
+    let mut reach = Reach::default();
+
+    explore(workflows, "in", workflows.all(), &mut vec![], &mut reach);
+
+    for cycle in reach.cycles.into_iter().sorted() {
+        problems.push(Problem::Cycle(
+            cycle.iter().map(|n| n.to_string()).collect(),
+        ));
+    }
+
+    for (name, rules) in workflows.rules.iter().sorted_by_key(|(name, _)| *name) {
+        if !reach.workflows.contains(&name[..]) {
+            problems.push(Problem::Unreachable(name.clone()));
+            continue;
+        }
+
+        for rule in (0..rules.len()).filter(|i| !reach.rules.contains(&(&name[..], *i))) {
+            problems.push(Problem::DeadRule {
+                workflow: name.clone(),
+                rule,
+            });
+        }
+    }
+
+    problems
+}
+
+/// The problems `validate` finds that the solution copes with, such as workflows and rules no part
+/// gets to. The rest break invariants, and input that does isn't looked at.
+pub fn warnings(file: &str) -> Vec<String> {
+    if invariants::check(file, INVARIANTS).is_err() {
+        return vec![];
+    }
+
+    validate(&parse(file).0)
+        .iter()
+        .map(|problem| problem.to_string())
+        .collect()
+}
+
+/// Workflows as boxes with an edge for each rule, labelled with its position and condition,
+/// leading to other workflows or to acceptance and rejection. Workflows and rules no part gets to
+/// are grey and dashed.
+pub fn dot(file: &str) -> String {
+    let (workflows, _) = parse(file);
+    let mut dot = Dot::new(&[("shape", "box")], &[("fontname", "monospace")]);
+    let problems = validate(&workflows);
+    let unused = [
+        ("style", "dashed"),
+        ("color", "grey"),
+        ("fontcolor", "grey"),
+    ];
+
+    dot.node("in", &[("style", "bold")]);
+    dot.node("A", &[("shape", "doublecircle"), ("color", "darkgreen")]);
+    dot.node("R", &[("shape", "circle"), ("color", "red")]);
+
+    for problem in &problems {
+        if let Problem::Unreachable(name) = problem {
+            dot.node(name, &unused);
+        }
+    }
+
+    for (name, rules) in workflows.rules.iter().sorted_by_key(|(name, _)| *name) {
+        for (i, rule) in rules.iter().enumerate() {
+            let condition = workflows.describe(&rule.condition);
+
+            let label = format!("{}: {condition}", i + 1);
+            let dead = problems.contains(&Problem::DeadRule {
+                workflow: name.clone(),
+                rule: i,
+            });
+
+            if dead {
+                dot.edge(
+                    name,
+                    &rule.destination,
+                    &[&[("label", &label[..])], &unused[..]].concat(),
+                );
+            } else {
+                dot.edge(name, &rule.destination, &[("label", &label)]);
+            }
+        }
+    }
+
+    dot.finish()
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(&input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
 }
 
 pub fn main() {
@@ -192,3 +953,71 @@ pub fn main() {
     assert_eq!(part1, 409898);
     assert_eq!(part2, 113057405770956);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(workflows: &str) -> Vec<Problem> {
+        validate(&parse(&format!("{workflows}\n\n{{x=1,m=1,a=1,s=1}}")).0)
+    }
+
+    #[test]
+    fn cycle() {
+        assert_eq!(
+            problems("in{x<10:aa,A}\naa{in}"),
+            vec![Problem::Cycle(vec![
+                "aa".to_string(),
+                "in".to_string(),
+                "aa".to_string()
+            ])]
+        );
+    }
+
+    #[test]
+    fn dangling() {
+        assert_eq!(
+            problems("in{x<10:zz,A}"),
+            vec![Problem::Dangling {
+                workflow: "in".to_string(),
+                destination: "zz".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn no_start() {
+        assert_eq!(problems("aa{A}"), vec![Problem::NoStart]);
+    }
+
+    #[test]
+    fn unreachable() {
+        assert_eq!(
+            problems("in{A}\naa{R}"),
+            vec![Problem::Unreachable("aa".to_string())]
+        );
+    }
+
+    #[test]
+    fn dead_rule() {
+        assert_eq!(
+            problems("in{x<10:R,x<5:A,A}"),
+            vec![Problem::DeadRule {
+                workflow: "in".to_string(),
+                rule: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn warnings_leave_out_what_breaks_invariants() {
+        assert_eq!(
+            warnings("in{x<10:R,x<5:A,A}\naa{R}\n\n{x=1,m=1,a=1,s=1}"),
+            vec![
+                "no part gets to aa",
+                "rule 2 of in matches none of the parts that get to it"
+            ]
+        );
+        assert!(warnings("in{x<10:zz,A}\n\n{x=1,m=1,a=1,s=1}").is_empty());
+    }
+}
@@ -1,15 +1,28 @@
-use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::dot::Dot;
+use advent_of_code_2023::generators;
+use advent_of_code_2023::invariants::{self, Invariant};
+use advent_of_code_2023::properties::{self, Property};
+use advent_of_code_2023::stats;
+use advent_of_code_2023::vcd::Vcd;
 use itertools::Itertools;
 use num::integer::gcd;
+use tracing::info_span;
 
 type Int = u64;
 type InputType = HashMap<String, Line>;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
-enum Module {
+/// How long to look for the feeders' cycles, or for a low pulse to rx without them
+const MAX_PRESSES: Int = 100_000;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Module {
     Broadcaster,
     FlipFlop(bool),
     Conjunction(BTreeMap<String, bool>),
@@ -18,7 +31,7 @@ enum Module {
 impl Module {
     fn pulse(&mut self, sender: String, pulse: bool) -> Option<bool> {
         match self {
-            Module::Broadcaster => None,
+            Module::Broadcaster => Some(pulse),
             Module::FlipFlop(state) => {
                 if pulse {
                     return None;
@@ -34,6 +47,15 @@ impl Module {
             }
         }
     }
+
+    /// The pulse the module last sent, or for a conjunction the one it would send next
+    pub fn level(&self) -> bool {
+        match self {
+            Module::Broadcaster => false,
+            Module::FlipFlop(state) => *state,
+            Module::Conjunction(state) => !state.values().all(|v| *v),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -44,14 +66,32 @@ enum ModuleTypes {
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Line {
+pub struct Line {
     module_type: ModuleTypes,
     children: Vec<String>,
 }
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("/input.txt").unwrap();
-
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "every line is a flip-flop, conjunction or the broadcaster with its outputs",
+        check: |input| {
+            invariants::lines_match(input, r"^([%&][a-z]+|broadcaster) -> [a-z]+(, [a-z]+)*$")
+        },
+    },
+    Invariant {
+        description: "there is exactly one broadcaster",
+        check: |input| match input
+            .lines()
+            .filter(|l| l.starts_with("broadcaster "))
+            .count()
+        {
+            1 => Ok(()),
+            n => Err(format!("found {n} broadcasters")),
+        },
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
     let mut modules = HashMap::new();
 
     for line in file.lines() {
@@ -85,7 +125,15 @@ fn read_input() -> InputType {
     modules
 }
 
-fn part1(modules: InputType) -> Int {
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day20/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
+fn get_parents_by_child(modules: &InputType) -> HashMap<String, Vec<String>> {
     let mut parents_by_child: HashMap<String, Vec<String>> = HashMap::new();
 
     for (k, v) in modules.iter() {
@@ -97,115 +145,546 @@ fn part1(modules: InputType) -> Int {
         })
     }
 
-    let mut state: HashMap<_, _> = HashMap::new();
+    parents_by_child
+}
+
+/// A pulse sent from one module to another, high or low
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Pulse {
+    pub from: String,
+    pub to: String,
+    pub high: bool,
+}
+
+/// Called with each pulse a module gets and the module's state once it has handled the pulse,
+/// which is `None` for outputs such as rx that aren't modules
+type Hook = Box<dyn FnMut(&Pulse, Option<&Module>)>;
+
+/// The modules and their states, kept from one button press to the next.
+pub struct Network {
+    modules: InputType,
+    parents_by_child: HashMap<String, Vec<String>>,
+    state: HashMap<String, Module>,
+    presses: Int,
+    hooks: Vec<(String, Hook)>,
+}
+
+impl Network {
+    pub fn new(modules: InputType) -> Network {
+        let parents_by_child = get_parents_by_child(&modules);
+        let mut network = Network {
+            modules,
+            parents_by_child,
+            state: HashMap::new(),
+            presses: 0,
+            hooks: vec![],
+        };
+
+        network.reset();
+        network
+    }
+
+    /// A module in its starting state, a flip-flop off and a conjunction remembering a low pulse
+    /// from each of its inputs
+    fn initial(&self, name: &str) -> Option<Module> {
+        Some(match self.modules.get(name)?.module_type {
+            ModuleTypes::Broadcaster => Module::Broadcaster,
+            ModuleTypes::FlipFlop => Module::FlipFlop(false),
+            ModuleTypes::Conjunction => Module::Conjunction(BTreeMap::from_iter(
+                self.parents_by_child
+                    .get(name)
+                    .into_iter()
+                    .flatten()
+                    .map(|parent| (parent.clone(), false)),
+            )),
+        })
+    }
+
+    /// Puts every module back in its starting state, as if the button had never been pressed
+    pub fn reset(&mut self) {
+        self.state = self
+            .modules
+            .keys()
+            .map(|name| (name.clone(), self.initial(name).unwrap()))
+            .collect();
+        self.presses = 0;
+    }
+
+    /// Puts one module back in its starting state, returning whether there is such a module
+    pub fn reset_module(&mut self, name: &str) -> bool {
+        let Some(module) = self.initial(name) else {
+            return false;
+        };
+
+        self.state.insert(name.to_string(), module);
+        true
+    }
+
+    pub fn module(&self, name: &str) -> Option<&Module> {
+        self.state.get(name)
+    }
+
+    pub fn presses(&self) -> Int {
+        self.presses
+    }
+
+    /// Calls `hook` with every pulse sent to the named module from now on
+    pub fn observe(&mut self, name: &str, hook: impl FnMut(&Pulse, Option<&Module>) + 'static) {
+        self.hooks.push((name.to_string(), Box::new(hook)));
+    }
+
+    /// A hash of every module's state, equal whenever the network is in the same state, to find
+    /// where it starts repeating
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        for name in self.state.keys().sorted() {
+            (name, &self.state[name]).hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    /// Sends a low pulse from the button to the broadcaster and delivers pulses until none are
+    /// left, returning them in the order they were delivered.
+    pub fn press_button(&mut self) -> Vec<Pulse> {
+        self.press_button_with(|_, _| {})
+    }
+
+    /// Like `press_button`, but also calling `on_pulse` after each pulse is handled, as the hooks
+    /// are.
+    pub fn press_button_with(
+        &mut self,
+        mut on_pulse: impl FnMut(&Pulse, Option<&Module>),
+    ) -> Vec<Pulse> {
+        let mut trace = vec![];
+        let mut queue = VecDeque::from([Pulse {
+            from: "button".to_string(),
+            to: "broadcaster".to_string(),
+            high: false,
+        }]);
+
+        self.presses += 1;
+
+        while let Some(pulse) = queue.pop_front() {
+            let sent = self
+                .state
+                .get_mut(&pulse.to)
+                .and_then(|module| module.pulse(pulse.from.clone(), pulse.high));
+            let receiver = self.state.get(&pulse.to);
+
+            on_pulse(&pulse, receiver);
+
+            for (_, hook) in self.hooks.iter_mut().filter(|(name, _)| *name == pulse.to) {
+                hook(&pulse, receiver);
+            }
+
+            if let Some(high) = sent {
+                for child in &self.modules[&pulse.to].children {
+                    queue.push_back(Pulse {
+                        from: pulse.to.clone(),
+                        to: child.clone(),
+                        high,
+                    });
+                }
+            }
+
+            trace.push(pulse);
+        }
+
+        trace
+    }
+}
+
+fn part1(modules: InputType) -> Int {
+    let mut network = Network::new(modules);
 
     let mut l: u64 = 0;
     let mut h = 0;
 
     for _ in 0..1000 {
-        l += 1; // Button click
+        for pulse in network.press_button() {
+            if pulse.high {
+                h += 1;
+            } else {
+                l += 1;
+            }
+        }
+    }
+
+    l * h
+}
 
-        let mut queue = VecDeque::from([("broadcaster".to_string(), false)]);
+/// The inputs of the conjunction feeding rx, which sends rx a low pulse once they have all sent
+/// it a high one.
+fn rx_feeders(modules: &InputType) -> Result<Vec<String>, String> {
+    let parents_by_child = get_parents_by_child(modules);
 
-        while let Some((head, pulse)) = queue.pop_front() {
-            let children = modules.get(&head).unwrap().children.clone();
+    let hub = match parents_by_child.get("rx").map(|parents| &parents[..]) {
+        Some([hub]) => hub,
+        Some(parents) => return Err(format!("rx is fed by {} modules", parents.len())),
+        None => return Err("nothing feeds rx".to_string()),
+    };
 
-            for child in children {
-                if pulse {
-                    h += 1;
-                } else {
-                    l += 1;
-                }
+    if modules[hub].module_type != ModuleTypes::Conjunction {
+        return Err(format!("rx is fed by {hub}, which is not a conjunction"));
+    }
+
+    Ok(parents_by_child[hub].iter().cloned().sorted().collect())
+}
+
+/// The press on which each feeder first sends a high pulse, checking that it sends the next one
+/// exactly as many presses later, so that it repeats cleanly from the start.
+fn feeder_periods(modules: &InputType, feeders: &[String]) -> Result<Vec<Int>, String> {
+    let mut network = Network::new(modules.clone());
+    let mut highs: HashMap<&str, Vec<Int>> = HashMap::new();
+
+    while network.presses() < MAX_PRESSES {
+        for pulse in network.press_button() {
+            let Some(feeder) = feeders.iter().find(|f| **f == pulse.from) else {
+                continue;
+            };
+
+            let presses = highs.entry(feeder).or_default();
 
-                let Some(v) = modules.get(&child) else {
-                    continue;
-                };
-
-                if let Some(new_pulse) = state
-                    .entry(child.clone())
-                    .or_insert_with(|| match v.module_type {
-                        ModuleTypes::Broadcaster => Module::Broadcaster,
-                        ModuleTypes::FlipFlop => Module::FlipFlop(false),
-                        ModuleTypes::Conjunction => Module::Conjunction(BTreeMap::from_iter(
-                            parents_by_child
-                                .get(&child)
-                                .unwrap()
-                                .iter()
-                                .map(|parent| (parent.clone(), false)),
-                        )),
-                    })
-                    .pulse(head.to_string(), pulse)
-                {
-                    queue.push_back((child, new_pulse));
-                };
+            if pulse.high && presses.last() != Some(&network.presses()) {
+                presses.push(network.presses());
             }
         }
+
+        if feeders
+            .iter()
+            .all(|f| highs.get(&f[..]).is_some_and(|p| p.len() >= 2))
+        {
+            break;
+        }
     }
 
-    l * h
+    feeders
+        .iter()
+        .map(|feeder| match highs.get(&feeder[..]).map(|p| &p[..]) {
+            Some([first, second, ..]) if *second == 2 * first => Ok(*first),
+            Some([first, second, ..]) => Err(format!(
+                "{feeder} sends high pulses on presses {first} and {second}, which isn't a clean cycle"
+            )),
+            _ => Err(format!(
+                "{feeder} doesn't send two high pulses within {MAX_PRESSES} presses"
+            )),
+        })
+        .collect()
+}
+
+/// Presses the button until rx gets a low pulse, for networks without the usual structure
+fn press_until_rx(modules: &InputType) -> Option<Int> {
+    let mut network = Network::new(modules.clone());
+
+    while network.presses() < MAX_PRESSES {
+        let trace = network.press_button();
+
+        if trace.iter().any(|pulse| pulse.to == "rx" && !pulse.high) {
+            return Some(network.presses());
+        }
+    }
+
+    None
+}
+
+/// A chain of flip-flops from the broadcaster counting presses in binary, lowest bit first. The
+/// flip-flops for the period's set bits feed a conjunction, which once they are all on sets the
+/// others and the lowest, carrying the count back round to zero, and sends a high pulse through
+/// an inverting feeder towards rx.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Counter {
+    pub flip_flops: Vec<String>,
+    pub conjunction: String,
+    pub feeder: String,
+    pub period: Int,
+}
+
+/// Follows the chain of flip-flops from `start`, reading its period from which of them feed the
+/// chain's conjunction, and checks the conjunction resets the chain as a counter's would.
+fn counter(
+    modules: &InputType,
+    parents_by_child: &HashMap<String, Vec<String>>,
+    start: &str,
+) -> Result<Counter, String> {
+    let is = |name: &str, module_type| {
+        modules
+            .get(name)
+            .is_some_and(|line| line.module_type == module_type)
+    };
+
+    let mut flip_flops: Vec<String> = vec![];
+    let mut others = BTreeSet::new();
+    let mut next = Some(start.to_string());
+
+    while let Some(name) = next {
+        if !is(&name, ModuleTypes::FlipFlop) {
+            return Err(format!(
+                "{name} is in the chain from {start} but isn't a flip-flop"
+            ));
+        }
+
+        if flip_flops.contains(&name) {
+            return Err(format!("the chain from {start} loops back to {name}"));
+        }
+
+        let (chain, rest): (Vec<_>, Vec<_>) = modules[&name]
+            .children
+            .iter()
+            .partition(|child| is(child, ModuleTypes::FlipFlop));
+
+        if chain.len() > 1 {
+            return Err(format!("{name} feeds {} flip-flops, not one", chain.len()));
+        }
+
+        flip_flops.push(name);
+        others.extend(rest);
+        next = chain.first().map(|child| child.to_string());
+    }
+
+    if flip_flops.len() >= Int::BITS as usize {
+        return Err(format!(
+            "the chain from {start} has {} flip-flops, too many for its period to fit in {} bits",
+            flip_flops.len(),
+            Int::BITS
+        ));
+    }
+
+    let [conjunction] = others.into_iter().collect_vec()[..] else {
+        return Err(format!(
+            "the chain from {start} feeds more than one module besides its flip-flops"
+        ));
+    };
+
+    if !is(conjunction, ModuleTypes::Conjunction) {
+        return Err(format!(
+            "the chain from {start} feeds {conjunction}, which isn't a conjunction"
+        ));
+    }
+
+    let (set, clear): (Vec<_>, Vec<_>) = flip_flops
+        .iter()
+        .partition(|flip_flop| modules[*flip_flop].children.contains(conjunction));
+    let period = flip_flops
+        .iter()
+        .enumerate()
+        .filter(|(_, flip_flop)| set.contains(flip_flop))
+        .map(|(i, _)| 1 << i)
+        .sum::<Int>();
+
+    if !set.contains(&&flip_flops[0]) || !set.contains(&flip_flops.last().unwrap()) {
+        return Err(format!(
+            "{conjunction} isn't fed by both the lowest and highest flip-flops from {start}"
+        ));
+    }
+
+    if parents_by_child[conjunction]
+        .iter()
+        .sorted()
+        .ne(set.iter().copied().sorted())
+    {
+        return Err(format!(
+            "{conjunction} has inputs from outside the chain from {start}"
+        ));
+    }
+
+    let (resets, outputs): (Vec<_>, Vec<_>) = modules[conjunction]
+        .children
+        .iter()
+        .partition(|child| flip_flops.contains(child));
+    let expected = clear.into_iter().chain([&flip_flops[0]]);
+
+    if resets.into_iter().sorted().ne(expected.sorted()) {
+        return Err(format!(
+            "{conjunction} doesn't reset exactly the lowest flip-flop and those it isn't fed by"
+        ));
+    }
+
+    let [feeder] = outputs[..] else {
+        return Err(format!(
+            "{conjunction} feeds {} modules outside its chain, not one",
+            outputs.len()
+        ));
+    };
+
+    if !is(feeder, ModuleTypes::Conjunction) || parents_by_child[feeder].len() != 1 {
+        return Err(format!(
+            "{feeder} isn't a conjunction inverting {conjunction} alone"
+        ));
+    }
+
+    Ok(Counter {
+        flip_flops,
+        conjunction: conjunction.clone(),
+        feeder: feeder.clone(),
+        period,
+    })
+}
+
+/// Breaks the network down into a counter from each of the broadcaster's outputs, ordered by
+/// feeder, checking that their feeders are exactly the inputs of the conjunction in front of rx.
+/// This reads the periods from the wiring alone, explaining where the network doesn't fit.
+pub fn counters(modules: &InputType) -> Result<Vec<Counter>, String> {
+    let feeders = rx_feeders(modules)?;
+    let parents_by_child = get_parents_by_child(modules);
+
+    let counters: Vec<Counter> = modules["broadcaster"]
+        .children
+        .iter()
+        .map(|start| counter(modules, &parents_by_child, start))
+        .collect::<Result<_, _>>()?;
+
+    let counters = counters
+        .into_iter()
+        .sorted_by(|a, b| a.feeder.cmp(&b.feeder))
+        .collect_vec();
+
+    if counters.iter().map(|c| &c.feeder).ne(feeders.iter()) {
+        return Err(format!(
+            "the counters feed {}, but rx's conjunction is fed by {}",
+            counters.iter().map(|c| &c.feeder).join(", "),
+            feeders.join(", ")
+        ));
+    }
+
+    Ok(counters)
 }
 
+pub const PROPERTIES: &[Property] = &[Property {
+    description: "counter periods read from the wiring agree with simulating the feeders",
+    check: |rng| {
+        let bits = rng.range(4..=8) as usize;
+        let input = generators::generator(20).unwrap()(rng, bits);
+        let modules = parse(&input);
+
+        properties::agree(
+            format!("{bits}-bit counters"),
+            counters(&modules).map(|counters| counters.iter().map(|c| c.period).collect_vec()),
+            rx_feeders(&modules).and_then(|feeders| feeder_periods(&modules, &feeders)),
+        )
+    },
+}];
+
+/// The conjunction feeding rx needs all its inputs high at once. They are expected to be binary
+/// counters sending a high pulse on cycles of their own, so they first line up at the lowest
+/// common multiple of the cycles. Where the wiring doesn't show counters, the cycles are found by
+/// simulating instead, and failing that, by pressing the button until rx gets a low pulse.
 fn part2(modules: InputType) -> Int {
-    let mut parents_by_child: HashMap<String, Vec<String>> = HashMap::new();
+    let periods = rx_feeders(&modules).and_then(|feeders| {
+        counters(&modules)
+            .map(|counters| counters.iter().map(|c| c.period).collect_vec())
+            .or_else(|structure| {
+                tracing::warn!("{structure}, so finding the feeders' cycles by simulating");
+
+                feeder_periods(&modules, &feeders)
+            })
+    });
+
+    match periods {
+        Ok(periods) => periods
+            .into_iter()
+            .fold(1, |acc, v| (v * acc) / gcd(v, acc)),
+        Err(reason) => {
+            tracing::warn!("{reason}, so pressing the button until rx gets a low pulse");
+
+            press_until_rx(&modules).unwrap_or_else(|| {
+                panic!("{reason}, and rx got no low pulse within {MAX_PRESSES} presses")
+            })
+        }
+    }
+}
 
-    for (k, v) in modules.iter() {
-        v.children.iter().for_each(|child| {
-            parents_by_child
-                .entry(child.clone())
-                .or_default()
-                .push(k.clone());
-        })
+/// A waveform of each module's level over `presses` button presses, a time step per pulse, with a
+/// `press` counter marking where each press starts. Levels are a flip-flop's state, what a
+/// conjunction would send and the last pulse an output such as rx received. `filter` picks out
+/// modules by name, keeping them all when empty.
+pub fn vcd(file: &str, presses: usize, filter: &[String]) -> Result<String, String> {
+    let modules = parse(file);
+
+    let names = modules
+        .iter()
+        .flat_map(|(name, line)| [name].into_iter().chain(&line.children))
+        .unique()
+        .sorted()
+        .collect_vec();
+
+    if let Some(name) = filter.iter().find(|name| !names.contains(name)) {
+        return Err(format!("no module named {name}"));
     }
 
-    let mut state: HashMap<_, _> = HashMap::new();
+    let mut vcd = Vcd::new("day20");
+    let press = vcd.wire("press", 32);
+    let signals: HashMap<_, _> = names
+        .into_iter()
+        .filter(|name| filter.is_empty() || filter.contains(name))
+        .map(|name| (name.clone(), vcd.wire(name, 1)))
+        .collect();
+    let mut network = Network::new(modules);
+
+    vcd.change(0, press, 0);
 
-    let mut parents = HashMap::from(
-        [("tx", 0), ("dd", 0), ("nz", 0), ("ph", 0)].map(|(k, v)| (k.to_string(), v)),
-    );
+    for (name, signal) in signals.iter().sorted_by_key(|(name, _)| *name) {
+        let level = network.module(name).is_some_and(Module::level);
 
-    for i in 0.. {
-        let mut queue = VecDeque::from([("broadcaster".to_string(), false)]);
+        vcd.change(0, *signal, level as u64);
+    }
 
-        while let Some((head, pulse)) = queue.pop_front() {
-            let children = modules.get(&head).unwrap().children.clone();
+    let mut time = 0;
 
-            if pulse {
-                if let Some(rx_parent) = parents.get_mut(&head) {
-                    *rx_parent = i + 1;
-                }
-            }
+    for i in 1..=presses {
+        time += 1;
+        vcd.change(time, press, i as u64);
+
+        network.press_button_with(|pulse, receiver| {
+            time += 1;
+
+            if let Some(signal) = signals.get(&pulse.to) {
+                let level = receiver.map_or(pulse.high, Module::level);
 
-            for child in children {
-                let Some(v) = modules.get(&child) else {
-                    continue;
-                };
-
-                if let Some(new_pulse) = state
-                    .entry(child.clone())
-                    .or_insert_with(|| match v.module_type {
-                        ModuleTypes::Broadcaster => Module::Broadcaster,
-                        ModuleTypes::FlipFlop => Module::FlipFlop(false),
-                        ModuleTypes::Conjunction => Module::Conjunction(BTreeMap::from_iter(
-                            parents_by_child
-                                .get(&child)
-                                .unwrap()
-                                .iter()
-                                .map(|parent| (parent.clone(), false)),
-                        )),
-                    })
-                    .pulse(head.to_string(), pulse)
-                {
-                    queue.push_back((child, new_pulse));
-                };
+                vcd.change(time, *signal, level as u64);
             }
+        });
+    }
+
+    Ok(vcd.finish())
+}
+
+/// Modules shaped by type, flip-flops as boxes and conjunctions as trapezia, with an edge to each
+/// module they send pulses to.
+pub fn dot(file: &str) -> String {
+    let modules = parse(file);
+    let mut dot = Dot::new(&[], &[]);
+
+    for (name, line) in modules.iter().sorted_by_key(|(name, _)| *name) {
+        let (label, shape) = match line.module_type {
+            ModuleTypes::Broadcaster => (name.clone(), "doubleoctagon"),
+            ModuleTypes::FlipFlop => (format!("%{name}"), "box"),
+            ModuleTypes::Conjunction => (format!("&{name}"), "invtrapezium"),
+        };
+
+        dot.node(name, &[("label", &label), ("shape", shape)]);
+
+        for child in &line.children {
+            dot.edge(name, child, &[]);
         }
+    }
 
-        if parents.values().all(|v| *v > 0) {
-            return parents.values().fold(1, |acc, v| (v * acc) / gcd(*v, acc));
+    // Outputs such as rx only receive pulses
+    for child in modules.values().flat_map(|line| &line.children).unique() {
+        if !modules.contains_key(child) {
+            dot.node(child, &[("shape", "circle"), ("style", "bold")]);
         }
     }
 
-    panic!();
+    dot.finish()
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
 }
 
 pub fn main() {
@@ -218,12 +697,17 @@ pub fn main() {
     let part1_elapsed = now.elapsed();
 
     now = Instant::now();
-    let part2 = part2(input);
+    let (part2, spans) = stats::collect(|| info_span!("part2").in_scope(|| part2(input)));
     let part2_elapsed = now.elapsed();
 
     println!("--- Day 20 ---");
     println!("Part 1: {}", part1);
     println!("Part 2: {}", part2);
+
+    for warning in spans.iter().flat_map(|span| &span.warnings) {
+        println!("Warning: {warning}");
+    }
+
     println!("Reading input took: {:.2?}", input_elapsed);
     println!("Part 1 took: {:.2?}", part1_elapsed);
     println!("Part 2 took: {:.2?}", part2_elapsed);
@@ -231,3 +715,65 @@ pub fn main() {
     assert_eq!(part1, 869395600);
     assert_eq!(part2, 232605773145467);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    /// The puzzle's second example, which is back where it started every four presses
+    const EXAMPLE: &str = "broadcaster -> a\n%a -> inv, con\n&inv -> b\n%b -> con\n&con -> output";
+
+    #[test]
+    fn reset_restores_the_state_hash() {
+        let mut network = Network::new(parse(EXAMPLE));
+        let start = network.state_hash();
+
+        network.press_button();
+        assert_ne!(network.state_hash(), start);
+
+        network.reset();
+        assert_eq!(network.state_hash(), start);
+        assert_eq!(network.presses(), 0);
+
+        for _ in 0..4 {
+            network.press_button();
+        }
+        assert_eq!(network.state_hash(), start);
+    }
+
+    #[test]
+    fn hooks_see_the_pulses_sent_to_their_module() {
+        let mut network = Network::new(parse(EXAMPLE));
+        let seen = Rc::new(RefCell::new(vec![]));
+        let sink = seen.clone();
+
+        network.observe("output", move |pulse, module| {
+            sink.borrow_mut()
+                .push((pulse.from.clone(), pulse.high, module.is_some()));
+        });
+        network.press_button();
+
+        assert_eq!(
+            *seen.borrow(),
+            [
+                ("con".to_string(), true, false),
+                ("con".to_string(), false, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn chains_too_long_for_a_period_are_rejected() {
+        let chain = (0..64)
+            .map(|i| format!("%f{i} -> f{}", i + 1))
+            .join("\n")
+            .replace("-> f64", "-> con");
+        let modules = parse(&format!("broadcaster -> f0\n{chain}\n&con -> f0"));
+
+        let error = counter(&modules, &get_parents_by_child(&modules), "f0").unwrap_err();
+        assert!(error.contains("64 flip-flops"), "{error}");
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use std::fs;
+use std::time::Instant;
+
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+use itertools::Itertools;
+
+type Int = u64;
+type Coord = (isize, isize);
+type InputType = (Vec<Vec<bool>>, Coord);
+
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the map only contains garden plots, rocks and one start",
+        check: |input| {
+            invariants::grid_chars(input, ".#S")?;
+            invariants::count_char(input, 'S', 1)
+        },
+    },
+    Invariant {
+        description: "the map is square with the start in the centre",
+        check: |input| {
+            let n = input.trim().lines().count();
+
+            invariants::grid_size(input, n, n)?;
+
+            let Some((row, line)) = input.trim().lines().find_position(|l| l.contains('S')) else {
+                return Err("there is no start".to_string());
+            };
+            let col = line.chars().position(|c| c == 'S').unwrap();
+
+            if row == n / 2 && col == n / 2 && n % 2 == 1 {
+                Ok(())
+            } else {
+                Err(format!("start is at ({row}, {col}) on a {n}x{n} map"))
+            }
+        },
+    },
+    Invariant {
+        description: "the start row and column are free of rocks, for the quadratic growth",
+        check: |input| {
+            let lines = input.trim().lines().collect_vec();
+            let centre = lines.len() / 2;
+
+            if lines.get(centre).is_some_and(|l| l.contains('#')) {
+                return Err("the start row has a rock".to_string());
+            }
+
+            match lines
+                .iter()
+                .position(|l| l.chars().nth(centre) == Some('#'))
+            {
+                Some(row) => Err(format!("the start column has a rock on row {}", row + 1)),
+                None => Ok(()),
+            }
+        },
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
+    let mut start = (0, 0);
+
+    let rocks = file
+        .trim()
+        .lines()
+        .enumerate()
+        .map(|(r, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(c, ch)| {
+                    if ch == 'S' {
+                        start = (r as isize, c as isize);
+                    }
+
+                    ch == '#'
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+
+    (rocks, start)
+}
+
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day21/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
+/// Counts the plots reachable in exactly `steps` steps, with the map repeating infinitely
+fn count_reachable((rocks, start): &InputType, steps: usize) -> Int {
+    let nr = rocks.len() as isize;
+    let nc = rocks[0].len() as isize;
+
+    let is_rock = |(r, c): Coord| rocks[r.rem_euclid(nr) as usize][c.rem_euclid(nc) as usize];
+
+    let mut seen = HashSet::from([*start]);
+    let mut frontier = vec![*start];
+
+    // Any plot reached on a step of the same parity can be revisited by stepping back and forth
+    let mut count = steps.is_multiple_of(2) as Int;
+
+    for step in 1..=steps {
+        let mut next = vec![];
+
+        for (r, c) in frontier {
+            for neighbour in [(r - 1, c), (r + 1, c), (r, c - 1), (r, c + 1)] {
+                if !is_rock(neighbour) && seen.insert(neighbour) {
+                    next.push(neighbour);
+                }
+            }
+        }
+
+        if step % 2 == steps % 2 {
+            count += next.len() as Int;
+        }
+
+        frontier = next;
+    }
+
+    count
+}
+
+fn part1(input: InputType) -> Int {
+    count_reachable(&input, 64)
+}
+
+/// The start row and column are clear and the map is square, so the reachable area grows
+/// quadratically with every map width walked. Fit a quadratic on the first three widths.
+fn extrapolate(input: &InputType, steps: usize) -> Int {
+    let n = input.0.len();
+    let remainder = steps % n;
+
+    let [a0, a1, a2] = [0, 1, 2].map(|i| count_reachable(input, remainder + i * n) as i64);
+
+    let x = (steps / n) as i64;
+
+    (a0 + x * (a1 - a0) + x * (x - 1) / 2 * (a2 - 2 * a1 + a0)) as Int
+}
+
+fn part2(input: InputType) -> Int {
+    extrapolate(&input, 26501365)
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
+/// The example counts plots reachable in 6 steps, then in 100 on the endless map for part 2, as
+/// it is too small to extrapolate from
+pub const EXAMPLE_PARTS: Parts<InputType> = Parts::Separate(
+    |input| count_reachable(&input, 6).into(),
+    |input| count_reachable(&input, 100).into(),
+);
+
+pub fn run_example(file: &str, report: &mut dyn FnMut(Answer)) {
+    EXAMPLE_PARTS.run(file, parse, report);
+}
+
+pub fn main() {
+    let mut now = Instant::now();
+    let input = read_input();
+    let input_elapsed = now.elapsed();
+
+    now = Instant::now();
+    let part1 = part1(input.clone());
+    let part1_elapsed = now.elapsed();
+
+    now = Instant::now();
+    let part2 = part2(input);
+    let part2_elapsed = now.elapsed();
+
+    println!("--- Day 21 ---");
+    println!("Part 1: {}", part1);
+    println!("Part 2: {}", part2);
+    println!("Reading input took: {:.2?}", input_elapsed);
+    println!("Part 1 took: {:.2?}", part1_elapsed);
+    println!("Part 2 took: {:.2?}", part2_elapsed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let example = parse(&fs::read_to_string("./src/bin/day21/ex.txt").unwrap());
+
+        assert_eq!(count_reachable(&example, 6), 16);
+        assert_eq!(count_reachable(&example, 10), 50);
+        assert_eq!(count_reachable(&example, 50), 1594);
+        assert_eq!(count_reachable(&example, 100), 6536);
+    }
+}
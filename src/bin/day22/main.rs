@@ -3,15 +3,46 @@ use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fs;
 use std::time::Instant;
 
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::dot::Dot;
+use advent_of_code_2023::invariants::{self, Invariant};
 use itertools::Itertools;
 
 type Int = usize;
 type Coord = (Int, Int, Int);
 type InputType = Vec<(Coord, Coord)>;
 
-fn read_input() -> InputType {
-    let file = fs::read_to_string("./src/bin/day22/input.txt").unwrap();
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "every line is a brick given by the coordinates of both ends",
+        check: |input| invariants::lines_match(input, r"^\d+,\d+,\d+~\d+,\d+,\d+$"),
+    },
+    Invariant {
+        description: "bricks fit in the 10x10 footprint, with each end no greater than the other",
+        check: |input| {
+            invariants::each_line(input, |line| {
+                let coords = line
+                    .split(&[',', '~'][..])
+                    .filter_map(|s| s.parse::<Int>().ok())
+                    .collect_vec();
+
+                let [x1, y1, z1, x2, y2, z2] = coords[..] else {
+                    return Err(format!("{line} is not a brick"));
+                };
+
+                if [x1, y1, x2, y2].iter().any(|c| *c >= N) {
+                    Err(format!("{line} is outside the footprint"))
+                } else if x1 > x2 || y1 > y2 || z1 > z2 {
+                    Err(format!("{line} ends before it starts"))
+                } else {
+                    Ok(())
+                }
+            })
+        },
+    },
+];
 
+pub fn parse(file: &str) -> InputType {
     let mut coords: InputType = Vec::new();
 
     for line in file.lines() {
@@ -36,6 +67,14 @@ fn read_input() -> InputType {
     coords
 }
 
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day22/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
 const N: Int = 10;
 
 // Height, block_idx
@@ -113,8 +152,11 @@ fn get_descendants_count(
     let mut all_descendants_and_self = BTreeSet::from([*block]);
     let mut queue = VecDeque::from_iter(children);
     let mut count = 0;
+    let mut iterations = 0;
 
     while let Some(curr) = queue.pop_front() {
+        iterations += 1;
+
         if all_descendants_and_self.contains(curr) {
             continue;
         }
@@ -142,6 +184,8 @@ fn get_descendants_count(
         }
     }
 
+    tracing::info!(queue_iterations = iterations);
+
     all_descendants_and_self.len() - 1
 }
 
@@ -166,6 +210,56 @@ fn part2(input: InputType) -> Int {
         .sum()
 }
 
+/// Bricks once settled, numbered from the lowest and labelled with where they come to rest, with
+/// an edge from each brick to those it supports. Bricks that are some brick's only support, so cannot be disintegrated, are red.
+pub fn dot(file: &str) -> String {
+    let input = parse(file);
+    let parents_by_blocks = get_parents_of_blocks(&input);
+    let sole_supports = parents_by_blocks
+        .values()
+        .filter(|parents| parents.len() == 1)
+        .flatten()
+        .collect::<BTreeSet<_>>();
+    let mut dot = Dot::new(&[("shape", "box")], &[("dir", "back")]);
+
+    // The top of each settled brick, starting with the ground's
+    let mut tops = vec![0];
+
+    dot.node("0", &[("label", "ground"), ("shape", "plaintext")]);
+
+    for (i, ((x1, y1, z1), (x2, y2, z2))) in (1..).zip(&input) {
+        // A brick comes to rest on its supports, which all have the same top
+        let support = parents_by_blocks[&i].first().unwrap();
+        let bottom = tops[*support] + 1;
+        let top = bottom + z2 - z1;
+        tops.push(top);
+
+        let label = format!("{i}\\n{x1},{y1},{bottom}~{x2},{y2},{top}");
+        let colour = if sole_supports.contains(&i) {
+            "red"
+        } else {
+            "black"
+        };
+
+        dot.node(&i.to_string(), &[("label", &label), ("color", colour)]);
+    }
+
+    for (block, parents) in &parents_by_blocks {
+        for parent in parents {
+            dot.edge(&block.to_string(), &parent.to_string(), &[]);
+        }
+    }
+
+    dot.finish()
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
 pub fn main() {
     let mut now = Instant::now();
     let input = read_input();
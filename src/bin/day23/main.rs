@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::fs;
+use std::time::Instant;
+
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+use itertools::Itertools;
+
+type Int = usize;
+type Coord = (usize, usize);
+type InputType = Vec<Vec<char>>;
+type Graph = Vec<Vec<(usize, Int)>>;
+
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "the map is a rectangle of paths, forest and slopes",
+        check: |input| {
+            invariants::rectangular(input)?;
+            invariants::grid_chars(input, "#.^v<>")
+        },
+    },
+    Invariant {
+        description: "the top and bottom rows each have exactly one opening",
+        check: |input| {
+            let first = input.trim().lines().next().unwrap_or_default();
+            let last = input.trim().lines().last().unwrap_or_default();
+
+            invariants::count_char(first, '.', 1)?;
+            invariants::count_char(last, '.', 1)
+        },
+    },
+    Invariant {
+        description: "there are at most 62 junctions, to fit in a bitmask with the start and end",
+        check: |input| {
+            let grid = parse(input);
+
+            let junctions = grid
+                .iter()
+                .enumerate()
+                .flat_map(|(r, row)| (0..row.len()).map(move |c| (r, c)))
+                .filter(|&(r, c)| grid[r][c] != '#' && is_junction(&grid, (r, c)))
+                .count();
+
+            if junctions <= 62 {
+                Ok(())
+            } else {
+                Err(format!("found {junctions} junctions"))
+            }
+        },
+    },
+];
+
+pub fn parse(file: &str) -> InputType {
+    file.trim()
+        .lines()
+        .map(|line| line.chars().collect_vec())
+        .collect_vec()
+}
+
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day23/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
+fn neighbours(grid: &InputType, (r, c): Coord, slippery: bool) -> Vec<Coord> {
+    let candidates = match (slippery, grid[r][c]) {
+        (true, '^') => vec![(r.wrapping_sub(1), c)],
+        (true, 'v') => vec![(r + 1, c)],
+        (true, '<') => vec![(r, c.wrapping_sub(1))],
+        (true, '>') => vec![(r, c + 1)],
+        _ => vec![
+            (r.wrapping_sub(1), c),
+            (r + 1, c),
+            (r, c.wrapping_sub(1)),
+            (r, c + 1),
+        ],
+    };
+
+    candidates
+        .into_iter()
+        .filter(|&(r, c)| {
+            grid.get(r)
+                .and_then(|row| row.get(c))
+                .is_some_and(|&t| t != '#')
+        })
+        .collect()
+}
+
+fn is_junction(grid: &InputType, coord: Coord) -> bool {
+    neighbours(grid, coord, false).len() > 2
+}
+
+/// Compresses the maze into a graph of junctions, where each edge is a corridor with its length.
+/// Returns the graph with the start as node 0 and the end as node 1.
+fn compress(grid: &InputType, slippery: bool) -> Graph {
+    let start = (0, grid[0].iter().position(|&t| t == '.').unwrap());
+    let last = grid.len() - 1;
+    let end = (last, grid[last].iter().position(|&t| t == '.').unwrap());
+
+    let mut junctions = vec![start, end];
+
+    for (r, row) in grid.iter().enumerate() {
+        for (c, &tile) in row.iter().enumerate() {
+            if tile != '#' && is_junction(grid, (r, c)) {
+                junctions.push((r, c));
+            }
+        }
+    }
+
+    let index: HashMap<Coord, usize> = junctions.iter().enumerate().map(|(i, &j)| (j, i)).collect();
+
+    let mut graph = vec![vec![]; junctions.len()];
+
+    for (i, &junction) in junctions.iter().enumerate() {
+        for first in neighbours(grid, junction, slippery) {
+            let mut prev = junction;
+            let mut curr = first;
+            let mut length = 1;
+
+            loop {
+                if let Some(&j) = index.get(&curr) {
+                    graph[i].push((j, length));
+                    break;
+                }
+
+                let Some(next) = neighbours(grid, curr, slippery)
+                    .into_iter()
+                    .find(|&n| n != prev)
+                else {
+                    // Dead end, or a slope pointing back the way we came
+                    break;
+                };
+
+                prev = curr;
+                curr = next;
+                length += 1;
+            }
+        }
+    }
+
+    graph
+}
+
+fn longest_path(graph: &Graph, node: usize, visited: u64) -> Option<Int> {
+    if node == 1 {
+        return Some(0);
+    }
+
+    // The end is only reachable through this junction, so any other route would get stuck
+    if let Some(&(_, length)) = graph[node].iter().find(|(next, _)| *next == 1) {
+        return Some(length);
+    }
+
+    graph[node]
+        .iter()
+        .filter(|(next, _)| visited & (1 << next) == 0)
+        .filter_map(|&(next, length)| {
+            longest_path(graph, next, visited | (1 << next)).map(|rest| rest + length)
+        })
+        .max()
+}
+
+fn solve(grid: &InputType, slippery: bool) -> Int {
+    let graph = compress(grid, slippery);
+
+    assert!(graph.len() <= 64, "Too many junctions for a u64 bitmask");
+
+    longest_path(&graph, 0, 1).unwrap()
+}
+
+fn part1(input: InputType) -> Int {
+    solve(&input, true)
+}
+
+fn part2(input: InputType) -> Int {
+    solve(&input, false)
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
+pub fn main() {
+    let mut now = Instant::now();
+    let input = read_input();
+    let input_elapsed = now.elapsed();
+
+    now = Instant::now();
+    let part1 = part1(input.clone());
+    let part1_elapsed = now.elapsed();
+
+    now = Instant::now();
+    let part2 = part2(input);
+    let part2_elapsed = now.elapsed();
+
+    println!("--- Day 23 ---");
+    println!("Part 1: {}", part1);
+    println!("Part 2: {}", part2);
+    println!("Reading input took: {:.2?}", input_elapsed);
+    println!("Part 1 took: {:.2?}", part1_elapsed);
+    println!("Part 2 took: {:.2?}", part2_elapsed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let example = parse(&fs::read_to_string("./src/bin/day23/ex.txt").unwrap());
+
+        assert_eq!(part1(example.clone()), 94);
+        assert_eq!(part2(example), 154);
+    }
+}
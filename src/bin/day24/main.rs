@@ -0,0 +1,274 @@
+use std::fs;
+use std::time::Instant;
+
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+use itertools::Itertools;
+use num::{BigInt, BigRational, One, Signed, Zero};
+
+type Int = i128;
+type Vector = [Int; 3];
+type InputType = Vec<(Vector, Vector)>;
+
+pub const INVARIANTS: &[Invariant] = &[
+    Invariant {
+        description: "every line is a position and a velocity of three integers",
+        check: |input| {
+            invariants::lines_match(input, r"^-?\d+, +-?\d+, +-?\d+ @ +-?\d+, +-?\d+, +-?\d+$")
+        },
+    },
+    Invariant {
+        description: "every position is under 2^50 and every velocity under 2^20 in magnitude",
+        check: coordinates_bounded,
+    },
+];
+
+/// `paths_cross` multiplies a position by two velocities and `cross` a position by a velocity, so
+/// these bounds keep every product well inside an i128
+const POSITION_BITS: u32 = 50;
+const VELOCITY_BITS: u32 = 20;
+
+fn coordinates_bounded(input: &str) -> Result<(), String> {
+    invariants::each_line(input, |line| {
+        // Lines without a velocity are the format invariant's to report
+        let Some((position, velocity)) = line.split_once('@') else {
+            return Ok(());
+        };
+
+        for (vector, bits) in [(position, POSITION_BITS), (velocity, VELOCITY_BITS)] {
+            for n in vector.split(',').map(str::trim) {
+                let value = n
+                    .parse::<Int>()
+                    .map_err(|_| format!("{n} is not an integer that fits in an i128"))?;
+
+                if value.unsigned_abs() >= 1 << bits {
+                    return Err(format!("{n} is not under 2^{bits} in magnitude"));
+                }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+pub fn parse(file: &str) -> InputType {
+    file.trim()
+        .lines()
+        .map(|line| {
+            let (position, velocity) = line.split_once('@').unwrap();
+
+            let parse_vector = |s: &str| {
+                s.split(',')
+                    .map(|n| n.trim().parse::<Int>().unwrap())
+                    .collect_vec()
+                    .try_into()
+                    .unwrap()
+            };
+
+            (parse_vector(position), parse_vector(velocity))
+        })
+        .collect_vec()
+}
+
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day24/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
+/// Whether the future xy-paths of two hailstones cross inside the test area, using only integer
+/// arithmetic by scaling everything by the determinant
+fn paths_cross(a: &(Vector, Vector), b: &(Vector, Vector), min: Int, max: Int) -> bool {
+    let ([apx, apy, _], [avx, avy, _]) = *a;
+    let ([bpx, bpy, _], [bvx, bvy, _]) = *b;
+
+    let det = avx * bvy - avy * bvx;
+
+    if det == 0 {
+        return false;
+    }
+
+    let (dx, dy) = (bpx - apx, bpy - apy);
+
+    // t = t_num / det and s = s_num / det are the times each hailstone reaches the crossing
+    let mut t_num = dx * bvy - dy * bvx;
+    let mut s_num = dx * avy - dy * avx;
+    let mut det = det;
+
+    if det < 0 {
+        (t_num, s_num, det) = (-t_num, -s_num, -det);
+    }
+
+    if t_num < 0 || s_num < 0 {
+        return false;
+    }
+
+    let x = apx * det + avx * t_num;
+    let y = apy * det + avy * t_num;
+
+    (min * det..=max * det).contains(&x) && (min * det..=max * det).contains(&y)
+}
+
+fn count_crossings(input: &InputType, min: Int, max: Int) -> usize {
+    input
+        .iter()
+        .tuple_combinations()
+        .filter(|(a, b)| paths_cross(a, b, min, max))
+        .count()
+}
+
+fn part1(input: InputType) -> Int {
+    count_crossings(&input, 200000000000000, 400000000000000) as Int
+}
+
+fn cross(a: Vector, b: Vector) -> Vector {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn sub(a: Vector, b: Vector) -> Vector {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Solves the square system `matrix * x = rhs` exactly, or `None` if it is singular
+fn solve_exact(
+    mut matrix: Vec<Vec<BigRational>>,
+    mut rhs: Vec<BigRational>,
+) -> Option<Vec<BigRational>> {
+    let n = rhs.len();
+
+    for col in 0..n {
+        let pivot = (col..n).find(|&r| !matrix[r][col].is_zero())?;
+
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+
+        let pivot_row = matrix[col].clone();
+        let pivot_rhs = rhs[col].clone();
+
+        for (r, row) in matrix.iter_mut().enumerate() {
+            if r == col || row[col].is_zero() {
+                continue;
+            }
+
+            let factor = &row[col] / &pivot_row[col];
+
+            for (value, pivot) in row.iter_mut().zip(&pivot_row) {
+                *value -= &factor * pivot;
+            }
+
+            rhs[r] -= &factor * &pivot_rhs;
+        }
+    }
+
+    Some((0..n).map(|i| &rhs[i] / &matrix[i][i]).collect())
+}
+
+/// The rock (P, V) hits every hailstone (p, v), so (P - p) x (V - v) = 0. Subtracting that
+/// equation for two hailstones cancels the non-linear P x V term, leaving
+/// P x (v_i - v_j) + (p_i - p_j) x V = p_i x v_i - p_j x v_j.
+/// Two such pairs give six linear equations in the six unknowns.
+fn throw_rock(input: &InputType) -> Option<[BigRational; 6]> {
+    let ratio = |n: Int| BigRational::from_integer(BigInt::from(n));
+
+    input.iter().tuple_combinations().find_map(|(a, b, c)| {
+        let mut matrix = vec![];
+        let mut rhs = vec![];
+
+        for other in [b, c] {
+            let dv = sub(a.1, other.1);
+            let dp = sub(a.0, other.0);
+            let constant = sub(cross(a.0, a.1), cross(other.0, other.1));
+
+            // Coefficients of P x dv and dp x V, one row per component
+            for (i, constant) in constant.into_iter().enumerate() {
+                let mut row = vec![0; 6];
+
+                for j in 0..3 {
+                    let mut unit = [0; 3];
+                    unit[j] = 1;
+
+                    row[j] = cross(unit, dv)[i];
+                    row[3 + j] = cross(dp, unit)[i];
+                }
+
+                matrix.push(row.into_iter().map(ratio).collect_vec());
+                rhs.push(ratio(constant));
+            }
+        }
+
+        solve_exact(matrix, rhs)?.try_into().ok()
+    })
+}
+
+fn part2(input: InputType) -> Int {
+    let rock = throw_rock(&input).expect("No three hailstones pin down the rock");
+
+    let sum = &rock[0] + &rock[1] + &rock[2];
+
+    assert!(
+        sum.denom().is_one(),
+        "Rock does not start on integer coordinates"
+    );
+
+    let sum = sum.to_integer();
+    assert!(sum.abs() < BigInt::from(Int::MAX));
+
+    sum.to_string().parse().unwrap()
+}
+
+pub const PARTS: Parts<InputType> =
+    Parts::Separate(|input| part1(input).into(), |input| part2(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
+/// The example's test area runs from 7 to 27
+pub const EXAMPLE_PARTS: Parts<InputType> = Parts::Separate(
+    |input| (count_crossings(&input, 7, 27) as Int).into(),
+    |input| part2(input).into(),
+);
+
+pub fn run_example(file: &str, report: &mut dyn FnMut(Answer)) {
+    EXAMPLE_PARTS.run(file, parse, report);
+}
+
+pub fn main() {
+    let mut now = Instant::now();
+    let input = read_input();
+    let input_elapsed = now.elapsed();
+
+    now = Instant::now();
+    let part1 = part1(input.clone());
+    let part1_elapsed = now.elapsed();
+
+    now = Instant::now();
+    let part2 = part2(input);
+    let part2_elapsed = now.elapsed();
+
+    println!("--- Day 24 ---");
+    println!("Part 1: {}", part1);
+    println!("Part 2: {}", part2);
+    println!("Reading input took: {:.2?}", input_elapsed);
+    println!("Part 1 took: {:.2?}", part1_elapsed);
+    println!("Part 2 took: {:.2?}", part2_elapsed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let example = parse(&fs::read_to_string("./src/bin/day24/ex.txt").unwrap());
+
+        assert_eq!(count_crossings(&example, 7, 27), 2);
+        assert_eq!(part2(example), 47);
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::time::Instant;
+
+use advent_of_code_2023::answer::{Answer, Parts};
+use advent_of_code_2023::invariants::{self, Invariant};
+
+type Int = usize;
+type InputType = Vec<Vec<usize>>;
+
+pub const INVARIANTS: &[Invariant] = &[Invariant {
+    description: "every line is a component followed by those it connects to",
+    check: |input| invariants::lines_match(input, r"^[a-z]+:( [a-z]+)+$"),
+}];
+
+pub fn parse(file: &str) -> InputType {
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    let mut graph: InputType = vec![];
+
+    let mut node = |name| {
+        let len = index.len();
+        let i = *index.entry(name).or_insert(len);
+
+        if i == graph.len() {
+            graph.push(vec![]);
+        }
+
+        i
+    };
+
+    let mut edges = vec![];
+
+    for line in file.trim().lines() {
+        let (name, others) = line.split_once(": ").unwrap();
+        let from = node(name);
+
+        for other in others.split_whitespace() {
+            edges.push((from, node(other)));
+        }
+    }
+
+    for (a, b) in edges {
+        graph[a].push(b);
+        graph[b].push(a);
+    }
+
+    graph
+}
+
+fn read_input() -> InputType {
+    let file = fs::read_to_string("./src/bin/day25/input.txt").unwrap();
+
+    invariants::ensure(&file, INVARIANTS);
+
+    parse(&file)
+}
+
+/// Pushes unit flows from `source` to `sink` until `limit` paths are found or none are left.
+/// Returns the flow and the nodes still reachable from `source` in the residual graph.
+fn max_flow(graph: &InputType, source: usize, sink: usize, limit: usize) -> (usize, Vec<bool>) {
+    let mut flow: HashMap<(usize, usize), i8> = HashMap::new();
+
+    for paths in 0.. {
+        let mut parent = vec![None; graph.len()];
+        parent[source] = Some(source);
+
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(node) = queue.pop_front() {
+            for &next in &graph[node] {
+                let used = flow.get(&(node, next)).copied().unwrap_or(0);
+
+                if parent[next].is_none() && used < 1 {
+                    parent[next] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if parent[sink].is_none() || paths == limit {
+            return (paths, parent.iter().map(|p| p.is_some()).collect());
+        }
+
+        let mut node = sink;
+
+        while node != source {
+            let prev = parent[node].unwrap();
+
+            *flow.entry((prev, node)).or_default() += 1;
+            *flow.entry((node, prev)).or_default() -= 1;
+
+            node = prev;
+        }
+    }
+
+    unreachable!()
+}
+
+/// The wires form a graph that splits into two groups by cutting exactly three edges. Any node
+/// in the other group has a max flow of three from node 0, and the residual graph gives the group.
+fn part1(input: InputType) -> Int {
+    for sink in 1..input.len() {
+        let (flow, reachable) = max_flow(&input, 0, sink, 4);
+
+        if flow == 3 {
+            let group = reachable.iter().filter(|r| **r).count();
+
+            return group * (input.len() - group);
+        }
+    }
+
+    panic!("No cut of three wires found");
+}
+
+pub const PARTS: Parts<InputType> = Parts::Single(|input| part1(input).into());
+
+pub fn run(file: &str, report: &mut dyn FnMut(Answer)) {
+    PARTS.run(file, parse, report);
+}
+
+pub fn main() {
+    let mut now = Instant::now();
+    let input = read_input();
+    let input_elapsed = now.elapsed();
+
+    now = Instant::now();
+    let part1 = part1(input);
+    let part1_elapsed = now.elapsed();
+
+    println!("--- Day 25 ---");
+    println!("Part 1: {}", part1);
+    println!("Reading input took: {:.2?}", input_elapsed);
+    println!("Part 1 took: {:.2?}", part1_elapsed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn example() {
+        let example = parse(&fs::read_to_string("./src/bin/day25/ex.txt").unwrap());
+
+        assert_eq!(part1(example), 54);
+    }
+}
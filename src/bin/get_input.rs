@@ -3,37 +3,8 @@ use std::io::Write;
 use std::path::Path;
 use std::{env, fs};
 
+use advent_of_code_2023::client::fetch_input;
 use dotenv::dotenv;
-use reqwest::header::USER_AGENT;
-use reqwest::{blocking, header::COOKIE};
-
-fn fetch_input(day: u8) -> String {
-    let url = format!("https://adventofcode.com/2023/day/{day}/input");
-
-    let session = env::var("SESSION").expect("AoC session ID must be set");
-
-    let client = blocking::Client::new();
-
-    let res = client
-        .get(url)
-        .header(COOKIE, format!("session={}", session))
-        .header(
-            USER_AGENT,
-            "github.com/alexttyip/advent-of-code-2023 by tsztoyip@gmail.com",
-        )
-        .send()
-        .expect("Error fetching input");
-
-    if res.status().is_client_error() {
-        panic!("Error fetching input - client error");
-    }
-
-    if res.status().is_server_error() {
-        panic!("Error fetching input - server error");
-    }
-
-    res.text().expect("Error fetching input")
-}
 
 fn setup_day(day: u8) {
     let day_dir = format!("./src/bin/day{day:02}");
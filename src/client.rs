@@ -0,0 +1,62 @@
+use std::env;
+
+use reqwest::blocking::{self, RequestBuilder};
+use reqwest::header::{COOKIE, USER_AGENT};
+
+use crate::history::Verdict;
+
+fn with_headers(request: RequestBuilder) -> RequestBuilder {
+    let session = env::var("SESSION").expect("AoC session ID must be set");
+
+    request
+        .header(COOKIE, format!("session={}", session))
+        .header(
+            USER_AGENT,
+            "github.com/alexttyip/advent-of-code-2023 by tsztoyip@gmail.com",
+        )
+}
+
+fn send(request: RequestBuilder) -> String {
+    let res = with_headers(request).send().expect("Error sending request");
+
+    if res.status().is_client_error() {
+        panic!("Error sending request - client error");
+    }
+
+    if res.status().is_server_error() {
+        panic!("Error sending request - server error");
+    }
+
+    res.text().expect("Error reading response")
+}
+
+pub fn fetch_input(day: u8) -> String {
+    let url = format!("https://adventofcode.com/2023/day/{day}/input");
+
+    send(blocking::Client::new().get(url))
+}
+
+pub fn submit_answer(day: u8, part: u8, answer: &str) -> Verdict {
+    let url = format!("https://adventofcode.com/2023/day/{day}/answer");
+    let level = part.to_string();
+
+    let body = send(
+        blocking::Client::new()
+            .post(url)
+            .form(&[("level", level.as_str()), ("answer", answer)]),
+    );
+
+    if body.contains("That's the right answer") {
+        Verdict::Correct
+    } else if body.contains("too low") {
+        Verdict::TooLow
+    } else if body.contains("too high") {
+        Verdict::TooHigh
+    } else if body.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if body.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Wrong
+    }
+}
@@ -0,0 +1,79 @@
+//! The solutions under `src/bin`, also compiled into the library so the runner can call into them.
+
+#[path = "bin/day01/main.rs"]
+pub mod day01;
+
+#[path = "bin/day02/main.rs"]
+pub mod day02;
+
+#[path = "bin/day03/main.rs"]
+pub mod day03;
+
+#[path = "bin/day04/main.rs"]
+pub mod day04;
+
+#[path = "bin/day05/main.rs"]
+pub mod day05;
+
+#[path = "bin/day06/main.rs"]
+pub mod day06;
+
+#[path = "bin/day07/main.rs"]
+pub mod day07;
+
+#[path = "bin/day08/main.rs"]
+pub mod day08;
+
+#[path = "bin/day09/main.rs"]
+pub mod day09;
+
+#[path = "bin/day10/main.rs"]
+pub mod day10;
+
+#[path = "bin/day11/main.rs"]
+pub mod day11;
+
+#[path = "bin/day12/main.rs"]
+pub mod day12;
+
+#[path = "bin/day13/main.rs"]
+pub mod day13;
+
+#[path = "bin/day14/main.rs"]
+pub mod day14;
+
+#[path = "bin/day15/main.rs"]
+pub mod day15;
+
+#[path = "bin/day16/main.rs"]
+pub mod day16;
+
+#[path = "bin/day17/main.rs"]
+pub mod day17;
+
+#[path = "bin/day17-new/main.rs"]
+pub mod day17_new;
+
+#[path = "bin/day18/main.rs"]
+pub mod day18;
+
+#[path = "bin/day19/main.rs"]
+pub mod day19;
+
+#[path = "bin/day20/main.rs"]
+pub mod day20;
+
+#[path = "bin/day21/main.rs"]
+pub mod day21;
+
+#[path = "bin/day22/main.rs"]
+pub mod day22;
+
+#[path = "bin/day23/main.rs"]
+pub mod day23;
+
+#[path = "bin/day24/main.rs"]
+pub mod day24;
+
+#[path = "bin/day25/main.rs"]
+pub mod day25;
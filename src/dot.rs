@@ -0,0 +1,78 @@
+//! Writes Graphviz DOT documents for puzzles that are graphs, to render with `dot -Tsvg`.
+
+use std::fmt::Write;
+
+use crate::days::*;
+
+/// A directed graph, built a node and an edge at a time.
+pub struct Dot {
+    body: String,
+}
+
+/// Quotes an ID or attribute value, leaving escapes such as `\n` in labels to Graphviz
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\\\""))
+}
+
+fn attributes(attrs: &[(&str, &str)]) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+
+    let attrs = attrs
+        .iter()
+        .map(|(key, value)| format!("{key}={}", quote(value)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(" [{attrs}]")
+}
+
+impl Dot {
+    /// A graph whose nodes and edges default to the given attributes
+    pub fn new(node_defaults: &[(&str, &str)], edge_defaults: &[(&str, &str)]) -> Dot {
+        let mut body = String::new();
+
+        if !node_defaults.is_empty() {
+            writeln!(body, "  node{};", attributes(node_defaults)).unwrap();
+        }
+
+        if !edge_defaults.is_empty() {
+            writeln!(body, "  edge{};", attributes(edge_defaults)).unwrap();
+        }
+
+        Dot { body }
+    }
+
+    pub fn node(&mut self, id: &str, attrs: &[(&str, &str)]) {
+        writeln!(self.body, "  {}{};", quote(id), attributes(attrs)).unwrap();
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str, attrs: &[(&str, &str)]) {
+        writeln!(
+            self.body,
+            "  {} -> {}{};",
+            quote(from),
+            quote(to),
+            attributes(attrs)
+        )
+        .unwrap();
+    }
+
+    pub fn finish(self) -> String {
+        format!("digraph {{\n{}}}\n", self.body)
+    }
+}
+
+/// Parses the input and describes it as a DOT graph
+pub type RenderDot = fn(&str) -> String;
+
+pub fn dot_renderer(day: u8) -> Option<RenderDot> {
+    Some(match day {
+        8 => day08::dot,
+        19 => day19::dot,
+        20 => day20::dot,
+        22 => day22::dot,
+        _ => return None,
+    })
+}
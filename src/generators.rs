@@ -0,0 +1,100 @@
+//! Seeded generators of random puzzle inputs that satisfy each day's invariants, for stress
+//! testing solutions beyond the single `input.txt`.
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+/// Produces an input whose scale grows with `size`, as described by each day's generator
+pub type Generate = fn(&mut Rng, usize) -> String;
+
+pub const DEFAULT_SIZE: usize = 20;
+
+pub fn generator(day: u8) -> Option<Generate> {
+    Some(match day {
+        1 => day01::generate,
+        2 => day02::generate,
+        3 => day03::generate,
+        4 => day04::generate,
+        5 => day05::generate,
+        6 => day06::generate,
+        7 => day07::generate,
+        8 => day08::generate,
+        9 => day09::generate,
+        10 => day10::generate,
+        11 => day11::generate,
+        12 => day12::generate,
+        13 => day13::generate,
+        14 => day14::generate,
+        15 => day15::generate,
+        16 => day16::generate,
+        17 => day17::generate,
+        18 => day18::generate,
+        19 => day19::generate,
+        20 => day20::generate,
+        21 => day21::generate,
+        22 => day22::generate,
+        23 => day23::generate,
+        24 => day24::generate,
+        25 => day25::generate,
+        _ => return None,
+    })
+}
+
+fn digit(rng: &mut Rng, range: std::ops::RangeInclusive<u64>) -> char {
+    char::from_digit(rng.range(range) as u32, 10).unwrap()
+}
+
+fn word(rng: &mut Rng, len: usize) -> String {
+    (0..len)
+        .map(|_| (b'a' + rng.range(0..=25) as u8) as char)
+        .collect()
+}
+
+/// `count` distinct words of `len` letters that are not in `taken`, which they are added to
+fn unique_words(rng: &mut Rng, count: usize, len: usize, taken: &mut Vec<String>) -> Vec<String> {
+    let mut words = vec![];
+
+    while words.len() < count {
+        let word = word(rng, len);
+
+        if !taken.contains(&word) {
+            taken.push(word.clone());
+            words.push(word);
+        }
+    }
+
+    words
+}
+
+fn grid(rows: usize, cols: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    (0..rows)
+        .map(|r| (0..cols).map(|c| cell(r, c)).collect::<String>())
+        .join("\n")
+}
@@ -0,0 +1,33 @@
+use itertools::Itertools;
+
+use super::{digit, word};
+use crate::rng::Rng;
+
+const SPELT_DIGITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// `size` lines mixing letters, digits and spelt digits, each with at least one digit.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut line = String::new();
+
+            for _ in 0..rng.range(1..=6) {
+                match rng.range(0..=2) {
+                    0 => line.push(digit(rng, 1..=9)),
+                    1 => line.push_str(rng.choose::<&str>(&SPELT_DIGITS)),
+                    _ => {
+                        let len = rng.range(1..=4) as usize;
+                        line.push_str(&word(rng, len));
+                    }
+                }
+            }
+
+            let i = rng.index(line.len() + 1);
+            line.insert(i, digit(rng, 1..=9));
+
+            line
+        })
+        .join("\n")
+}
@@ -0,0 +1,26 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+/// `size` games of one to six draws, each showing some of the three colours.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (1..=size)
+        .map(|game| {
+            let draws = (0..rng.range(1..=6))
+                .map(|_| {
+                    let mut colours = ["red", "green", "blue"];
+                    rng.shuffle(&mut colours);
+
+                    let count = rng.range(1..=3) as usize;
+
+                    colours[..count]
+                        .iter()
+                        .map(|colour| format!("{} {colour}", rng.range(1..=20)))
+                        .join(", ")
+                })
+                .join("; ");
+
+            format!("Game {game}: {draws}")
+        })
+        .join("\n")
+}
@@ -0,0 +1,36 @@
+use itertools::Itertools;
+
+use super::digit;
+use crate::rng::Rng;
+
+const N: usize = 140;
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '@', '%', '=', '&', '-'];
+
+/// A 140x140 schematic, the only size the solution reads, where `size` is the percentage of
+/// positions that start a number or hold a symbol.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let density = size.clamp(1, 100) as u64;
+
+    (0..N)
+        .map(|_| {
+            let mut row = String::new();
+
+            while row.len() < N {
+                let after_number = row.ends_with(|c: char| c.is_ascii_digit());
+
+                if !rng.chance(density, 100) {
+                    row.push('.');
+                } else if after_number || rng.chance(1, 3) {
+                    row.push(*rng.choose(&SYMBOLS));
+                } else {
+                    let len = rng.range(1..=3).min((N - row.len()) as u64);
+
+                    row.push(digit(rng, 1..=9));
+                    (1..len).for_each(|_| row.push(digit(rng, 0..=9)));
+                }
+            }
+
+            row
+        })
+        .join("\n")
+}
@@ -0,0 +1,29 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const WINNING: usize = 10;
+const HAVE: usize = 25;
+
+/// `size` cards of 10 winning numbers and 25 numbers you have. No card wins copies of cards past
+/// the end of the table.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (1..=size)
+        .map(|card| {
+            let mut numbers = (1..=99).collect_vec();
+            rng.shuffle(&mut numbers);
+
+            let (winning, others) = numbers.split_at(WINNING);
+            let matches = rng.index(WINNING.min(size - card) + 1);
+
+            let mut have = [&winning[..matches], &others[..HAVE - matches]].concat();
+            rng.shuffle(&mut have);
+
+            format!(
+                "Card {card:>3}: {} | {}",
+                winning.iter().map(|n| format!("{n:>2}")).join(" "),
+                have.iter().map(|n| format!("{n:>2}")).join(" ")
+            )
+        })
+        .join("\n")
+}
@@ -0,0 +1,67 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// Seeds and maps over numbers below `100 * size`. Each map moves consecutive blocks of sources,
+/// sometimes with gaps between them, onto a shuffled run of destinations.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let scale = 100 * size.max(1) as u64;
+
+    let seeds = (0..rng.range(1..=5))
+        .map(|_| format!("{} {}", rng.range(0..=scale), rng.range(1..=scale / 10)))
+        .join(" ");
+
+    let maps = CATEGORIES
+        .iter()
+        .tuple_windows()
+        .map(|(from, to)| {
+            let blocks = rng.range(1..=size.max(1) as u64);
+            let lengths = (0..blocks)
+                .map(|_| rng.range(1..=scale / blocks))
+                .collect_vec();
+
+            let mut source = rng.range(0..=scale / 4);
+            let mut sources = vec![];
+
+            for length in &lengths {
+                if rng.chance(1, 3) {
+                    source += rng.range(1..=scale / 10);
+                }
+
+                sources.push(source);
+                source += length;
+            }
+
+            let mut order = (0..lengths.len()).collect_vec();
+            rng.shuffle(&mut order);
+
+            let mut destination = rng.range(0..=scale / 4);
+            let mut destinations = vec![0; lengths.len()];
+
+            for i in order {
+                destinations[i] = destination;
+                destination += lengths[i];
+            }
+
+            let mut lines = (0..lengths.len())
+                .map(|i| format!("{} {} {}", destinations[i], sources[i], lengths[i]))
+                .collect_vec();
+            rng.shuffle(&mut lines);
+
+            format!("{from}-to-{to} map:\n{}", lines.join("\n"))
+        })
+        .join("\n\n");
+
+    format!("seeds: {seeds}\n\n{maps}")
+}
@@ -0,0 +1,21 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+/// One to four races that can each be won, with times below `size` (at least 7).
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let races = (0..rng.range(1..=4))
+        .map(|_| {
+            let time = rng.range(2..=size.clamp(7, 99) as u64);
+            let best = (time / 2) * (time - time / 2);
+
+            (time, rng.range(0..=best - 1))
+        })
+        .collect_vec();
+
+    format!(
+        "Time:     {}\nDistance: {}",
+        races.iter().map(|(t, _)| format!("{t:>6}")).join(" "),
+        races.iter().map(|(_, d)| format!("{d:>6}")).join(" ")
+    )
+}
@@ -0,0 +1,29 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const CARDS: [char; 13] = [
+    'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+];
+
+/// `size` distinct hands, each with a bid of at most 1000.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut hands = HashSet::new();
+    let mut lines = vec![];
+
+    while lines.len() < size {
+        // Draw from a few cards per hand so pairs and better come up often
+        let pool = (0..rng.range(1..=5))
+            .map(|_| *rng.choose(&CARDS))
+            .collect_vec();
+        let hand = (0..5).map(|_| *rng.choose(&pool)).collect::<String>();
+
+        if hands.insert(hand.clone()) {
+            lines.push(format!("{hand} {}", rng.range(1..=1000)));
+        }
+    }
+
+    lines.join("\n")
+}
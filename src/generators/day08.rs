@@ -0,0 +1,58 @@
+use std::collections::HashSet;
+
+use crate::rng::Rng;
+
+fn name(rng: &mut Rng, taken: &mut HashSet<String>, last: Option<char>) -> String {
+    loop {
+        let mut name = (0..3)
+            .map(|_| (b'A' + rng.range(0..=25) as u8) as char)
+            .collect::<String>();
+
+        match last {
+            Some(c) => name.replace_range(2.., &c.to_string()),
+            None if name.ends_with(['A', 'Z']) => continue,
+            None => {}
+        }
+
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+/// Up to `size` instructions and two to six separate ghost cycles, one from AAA to ZZZ, each of
+/// up to `size` nodes. Every cycle returns to the node after its start once it reaches its end,
+/// so the steps between ends always match the steps to the first one.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2) as u64;
+
+    let instructions = (0..rng.range(1..=size))
+        .map(|_| *rng.choose(&['L', 'R']))
+        .collect::<String>();
+
+    let mut taken = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut lines = vec![];
+
+    for ghost in 0..rng.range(2..=6) {
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (
+                name(rng, &mut taken, Some('A')),
+                name(rng, &mut taken, Some('Z')),
+            )
+        };
+
+        let mut path = vec![start];
+        path.extend((1..rng.range(2..=size)).map(|_| name(rng, &mut taken, None)));
+        path.push(end);
+
+        for (from, to) in path.iter().zip(path.iter().skip(1).chain([&path[1]])) {
+            lines.push(format!("{from} = ({to}, {to})"));
+        }
+    }
+
+    rng.shuffle(&mut lines);
+
+    format!("{instructions}\n\n{}", lines.join("\n"))
+}
@@ -0,0 +1,25 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const N: i64 = 21;
+
+/// `size` histories of 21 values of polynomials of degree at most five.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let coefficients = (0..=rng.range(0..=5))
+                .map(|_| rng.signed(-10..=10))
+                .collect_vec();
+
+            (0..N)
+                .map(|x| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |value, coefficient| value * x + coefficient)
+                })
+                .join(" ")
+        })
+        .join("\n")
+}
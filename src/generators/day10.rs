@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::rng::Rng;
+
+const N: usize = 140;
+const PIPES: [char; 6] = ['|', '-', 'L', 'J', '7', 'F'];
+
+type Cells = Vec<Vec<bool>>;
+
+fn filled(cells: &Cells, r: isize, c: isize) -> bool {
+    r >= 0
+        && c >= 0
+        && cells
+            .get(r as usize)
+            .and_then(|row| row.get(c as usize))
+            .is_some_and(|cell| *cell)
+}
+
+/// Adding a cell keeps the shape free of holes and of cells touching only at a corner when its
+/// filled neighbours, going round it, form a single run that includes a side.
+fn can_fill(cells: &Cells, r: isize, c: isize) -> bool {
+    let ring = [
+        (-1, 0),
+        (-1, 1),
+        (0, 1),
+        (1, 1),
+        (1, 0),
+        (1, -1),
+        (0, -1),
+        (-1, -1),
+    ]
+    .map(|(dr, dc)| filled(cells, r + dr, c + dc));
+
+    let runs = (0..8).filter(|&i| ring[i] && !ring[(i + 7) % 8]).count();
+    let touches_side = [0, 2, 4, 6].iter().any(|&i| ring[i]);
+
+    !filled(cells, r, c) && runs == 1 && touches_side
+}
+
+/// Grows a random shape of cells without holes, then walks its outline, returning the corners
+/// in order round the loop.
+fn outline(rng: &mut Rng, size: usize) -> Vec<(usize, usize)> {
+    let mut cells = vec![vec![false; size]; size];
+    cells[rng.index(size)][rng.index(size)] = true;
+
+    for _ in 0..size * size * 4 {
+        let (r, c) = (rng.index(size) as isize, rng.index(size) as isize);
+
+        if can_fill(&cells, r, c) {
+            cells[r as usize][c as usize] = true;
+        }
+    }
+
+    // Corners joined by an edge with a filled cell on one side only
+    let mut edges: HashMap<(isize, isize), Vec<(isize, isize)>> = HashMap::new();
+
+    for r in 0..=size as isize {
+        for c in 0..=size as isize {
+            if filled(&cells, r - 1, c) != filled(&cells, r, c) {
+                edges.entry((r, c)).or_default().push((r, c + 1));
+                edges.entry((r, c + 1)).or_default().push((r, c));
+            }
+
+            if filled(&cells, r, c - 1) != filled(&cells, r, c) {
+                edges.entry((r, c)).or_default().push((r + 1, c));
+                edges.entry((r + 1, c)).or_default().push((r, c));
+            }
+        }
+    }
+
+    let start = *edges.keys().min().unwrap();
+    let mut corners = vec![start];
+    let mut prev = start;
+    let mut curr = edges[&start][0];
+
+    while curr != start {
+        corners.push(curr);
+
+        let next = *edges[&curr].iter().find(|next| **next != prev).unwrap();
+        (prev, curr) = (curr, next);
+    }
+
+    corners
+        .into_iter()
+        .map(|(r, c)| (r as usize, c as usize))
+        .collect()
+}
+
+fn pipe(from: (usize, usize), at: (usize, usize), to: (usize, usize)) -> char {
+    let direction = |other: (usize, usize)| match (
+        other.0 as isize - at.0 as isize,
+        other.1 as isize - at.1 as isize,
+    ) {
+        (-1, 0) => 'N',
+        (1, 0) => 'S',
+        (0, -1) => 'W',
+        _ => 'E',
+    };
+
+    let mut ends = [direction(from), direction(to)];
+    ends.sort();
+
+    match ends {
+        ['N', 'S'] => '|',
+        ['E', 'W'] => '-',
+        ['E', 'N'] => 'L',
+        ['N', 'W'] => 'J',
+        ['S', 'W'] => '7',
+        _ => 'F',
+    }
+}
+
+/// A 140x140 map, the only size the solution reads, with the outline of a random shape of up to
+/// `size`x`size` cells as the loop. Tiles off the loop are ground or stray pipes, except next to
+/// the start, so only the loop connects to it.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(1, N - 1);
+    let corners = outline(rng, size);
+
+    let offset = (rng.index(N - size), rng.index(N - size));
+    let position = |(r, c): (usize, usize)| (r + offset.0, c + offset.1);
+
+    let mut map = (0..N)
+        .map(|_| {
+            (0..N)
+                .map(|_| {
+                    if rng.chance(1, 2) {
+                        '.'
+                    } else {
+                        *rng.choose(&PIPES)
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let len = corners.len();
+
+    for i in 0..len {
+        let (r, c) = position(corners[i]);
+        map[r][c] = pipe(
+            position(corners[(i + len - 1) % len]),
+            (r, c),
+            position(corners[(i + 1) % len]),
+        );
+    }
+
+    let start = position(corners[rng.index(len)]);
+    let on_loop = |p: (usize, usize)| corners.iter().any(|&corner| position(corner) == p);
+
+    for (dr, dc) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        let (r, c) = (start.0 as isize + dr, start.1 as isize + dc);
+
+        if (0..N as isize).contains(&r)
+            && (0..N as isize).contains(&c)
+            && !on_loop((r as usize, c as usize))
+        {
+            map[r as usize][c as usize] = '.';
+        }
+    }
+
+    map[start.0][start.1] = 'S';
+
+    map.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
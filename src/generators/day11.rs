@@ -0,0 +1,17 @@
+use super::grid;
+use crate::rng::Rng;
+
+/// A `size`x`size` image where about one in five rows and columns are left empty.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let empty_rows = (0..size).map(|_| rng.chance(1, 5)).collect::<Vec<_>>();
+    let empty_cols = (0..size).map(|_| rng.chance(1, 5)).collect::<Vec<_>>();
+
+    grid(size, size, |r, c| {
+        if !empty_rows[r] && !empty_cols[c] && rng.chance(1, 8) {
+            '#'
+        } else {
+            '.'
+        }
+    })
+}
@@ -0,0 +1,34 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+/// `size` rows of up to 20 springs, with about half of each row's conditions unknown. The group
+/// sizes come from a real arrangement, so every row has at least one.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let len = rng.range(1..=20) as usize;
+            let mut springs = (0..len)
+                .map(|_| if rng.chance(1, 2) { '#' } else { '.' })
+                .collect_vec();
+
+            if !springs.contains(&'#') {
+                springs[rng.index(len)] = '#';
+            }
+
+            let groups = springs
+                .iter()
+                .dedup_with_count()
+                .filter(|(_, c)| **c == '#')
+                .map(|(n, _)| n)
+                .join(",");
+
+            let row = springs
+                .iter()
+                .map(|c| if rng.chance(1, 2) { '?' } else { *c })
+                .collect::<String>();
+
+            format!("{row} {groups}")
+        })
+        .join("\n")
+}
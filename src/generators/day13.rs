@@ -0,0 +1,124 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+type Pattern = Vec<Vec<bool>>;
+
+fn transpose(pattern: &Pattern) -> Pattern {
+    (0..pattern[0].len())
+        .map(|c| pattern.iter().map(|row| row[c]).collect())
+        .collect()
+}
+
+/// How many cells differ when reflecting about the line above each row after the first
+fn row_differences(pattern: &Pattern) -> Vec<usize> {
+    (1..pattern.len())
+        .map(|line| {
+            (0..line.min(pattern.len() - line))
+                .map(|k| {
+                    pattern[line - 1 - k]
+                        .iter()
+                        .zip(&pattern[line + k])
+                        .filter(|(a, b)| a != b)
+                        .count()
+                })
+                .sum()
+        })
+        .collect()
+}
+
+fn find(parent: &mut [usize], i: usize) -> usize {
+    if parent[i] != i {
+        parent[i] = find(parent, parent[i]);
+    }
+
+    parent[i]
+}
+
+/// A pattern with a perfect reflection between rows and a reflection between columns that is off
+/// by one smudge, and no other reflection that is perfect or off by one.
+fn pattern(rng: &mut Rng, max: usize) -> Pattern {
+    loop {
+        let rows = rng.range(5..=max as u64) as usize;
+        let cols = rng.range(5..=max as u64) as usize;
+        let row_line = rng.range(1..=rows as u64 - 1) as usize;
+        let col_line = rng.range(1..=cols as u64 - 1) as usize;
+
+        let mirror =
+            |i: usize, line: usize, len: usize| (2 * line).checked_sub(i + 1).filter(|m| *m < len);
+
+        // Cells that are mirror images about either line must match
+        let mut parent = (0..rows * cols).collect_vec();
+
+        for (r, c) in (0..rows).cartesian_product(0..cols) {
+            for other in [
+                mirror(r, row_line, rows).map(|m| m * cols + c),
+                mirror(c, col_line, cols).map(|m| r * cols + m),
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let (a, b) = (find(&mut parent, r * cols + c), find(&mut parent, other));
+                parent[a] = b;
+            }
+        }
+
+        let colours = (0..rows * cols).map(|_| rng.chance(1, 2)).collect_vec();
+
+        let mut pattern = (0..rows)
+            .map(|r| {
+                (0..cols)
+                    .map(|c| colours[find(&mut parent, r * cols + c)])
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        // Flipping a cell with no image across the row line leaves that reflection perfect, while
+        // its image across the column line becomes the one smudge
+        let smudges = (0..rows)
+            .cartesian_product(0..cols)
+            .filter(|&(r, c)| {
+                mirror(r, row_line, rows).is_none() && mirror(c, col_line, cols).is_some()
+            })
+            .collect_vec();
+
+        if smudges.is_empty() {
+            continue;
+        }
+
+        let (r, c) = *rng.choose(&smudges);
+        pattern[r][c] = !pattern[r][c];
+
+        let differences = [
+            row_differences(&pattern),
+            row_differences(&transpose(&pattern)),
+        ]
+        .concat();
+
+        if differences.iter().filter(|d| **d == 0).count() == 1
+            && differences.iter().filter(|d| **d == 1).count() == 1
+        {
+            return if rng.chance(1, 2) {
+                pattern
+            } else {
+                transpose(&pattern)
+            };
+        }
+    }
+}
+
+/// `size` patterns of 5 to 17 rows and columns.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            pattern(rng, 17)
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&rock| if rock { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .join("\n")
+        })
+        .join("\n\n")
+}
@@ -0,0 +1,20 @@
+use super::grid;
+use crate::rng::Rng;
+
+const N: usize = 100;
+
+/// A 100x100 platform, the only size the solution reads, where `size` is the percentage of
+/// positions holding round rocks. About one in eight holds a cube rock.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let density = size.min(100) as u64;
+
+    grid(N, N, |_, _| {
+        if rng.chance(1, 8) {
+            '#'
+        } else if rng.chance(density, 100) {
+            'O'
+        } else {
+            '.'
+        }
+    })
+}
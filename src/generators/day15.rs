@@ -0,0 +1,26 @@
+use itertools::Itertools;
+
+use super::word;
+use crate::rng::Rng;
+
+/// `size` steps over about half as many labels, each setting a focal length or removing a lens.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let labels = (0..size / 2 + 1)
+        .map(|_| {
+            let len = rng.range(1..=6) as usize;
+            word(rng, len)
+        })
+        .collect_vec();
+
+    (0..size.max(1))
+        .map(|_| {
+            let label = rng.choose(&labels);
+
+            if rng.chance(2, 3) {
+                format!("{label}={}", rng.range(1..=9))
+            } else {
+                format!("{label}-")
+            }
+        })
+        .join(",")
+}
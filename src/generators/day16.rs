@@ -0,0 +1,19 @@
+use super::grid;
+use crate::rng::Rng;
+
+const N: usize = 110;
+const DEVICES: [char; 4] = ['|', '-', '/', '\\'];
+
+/// A 110x110 contraption, the only size the solution reads, where `size` is the percentage of
+/// positions holding a mirror or splitter.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let density = size.min(100) as u64;
+
+    grid(N, N, |_, _| {
+        if rng.chance(density, 100) {
+            *rng.choose(&DEVICES)
+        } else {
+            '.'
+        }
+    })
+}
@@ -0,0 +1,11 @@
+use super::{digit, grid};
+use crate::rng::Rng;
+
+/// A map of 5 to `size` blocks a side, the smallest the ultra crucible can always cross.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max = size.max(5) as u64;
+    let rows = rng.range(5..=max) as usize;
+    let cols = rng.range(5..=max) as usize;
+
+    grid(rows, cols, |_, _| digit(rng, 1..=9))
+}
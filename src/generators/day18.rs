@@ -0,0 +1,59 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+/// The outline of `columns` side-by-side columns of random widths and heights on a flat base,
+/// which never crosses itself, as directions and distances.
+fn skyline(rng: &mut Rng, columns: usize, max: u64) -> Vec<(char, u64)> {
+    let mut heights: Vec<u64> = vec![];
+
+    while heights.len() < columns {
+        let height = rng.range(1..=max);
+
+        if heights.last() != Some(&height) {
+            heights.push(height);
+        }
+    }
+
+    let widths = (0..columns).map(|_| rng.range(1..=max)).collect_vec();
+
+    let mut plan = vec![('U', heights[0])];
+    let mut height = heights[0];
+
+    for (i, width) in widths.iter().enumerate() {
+        plan.push(('R', *width));
+
+        let next = heights.get(i + 1).copied().unwrap_or(0);
+
+        if next > height {
+            plan.push(('U', next - height));
+        } else {
+            plan.push(('D', height - next));
+        }
+
+        height = next;
+    }
+
+    plan.push(('L', widths.iter().sum()));
+    plan
+}
+
+/// Two outlines of `size` columns each, one as the plan and one encoded in the colours.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+
+    skyline(rng, size, 10)
+        .into_iter()
+        .zip(skyline(rng, size, 0xfffff / size as u64))
+        .map(|((direction, distance), (hex_direction, hex_distance))| {
+            let code = match hex_direction {
+                'R' => 0,
+                'D' => 1,
+                'L' => 2,
+                _ => 3,
+            };
+
+            format!("{direction} {distance} (#{hex_distance:05x}{code})")
+        })
+        .join("\n")
+}
@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+
+use itertools::Itertools;
+
+use super::unique_words;
+use crate::rng::Rng;
+
+const XMAS: [&str; 4] = ["x", "m", "a", "s"];
+
+/// Mostly `<` and `>`, as in the puzzle, with the rest of the comparisons now and then
+const OPERATORS: [&str; 8] = ["<", ">", "<", ">", "<=", ">=", "==", "!="];
+
+/// Accept, reject or, if `force` or by chance, a workflow not used yet, which is queued
+fn destination(
+    rng: &mut Rng,
+    unused: &mut Vec<String>,
+    queue: &mut VecDeque<String>,
+    force: bool,
+) -> String {
+    if force || rng.chance(2, 3) {
+        if let Some(name) = unused.pop() {
+            queue.push_back(name.clone());
+            return name;
+        }
+    }
+
+    rng.choose(&["A", "R"]).to_string()
+}
+
+/// Beyond this many, only the parts grow, which keeps three letters enough to name workflows and
+/// lets benchmarks sort millions of parts
+const MAX_WORKFLOWS: usize = 1000;
+
+/// A tree of `size` workflows from `in`, up to `MAX_WORKFLOWS`, so every workflow is reachable
+/// and no part is sent round in circles, followed by `size` parts. A quarter of inputs rate two to
+/// six categories of their own rather than x, m, a and s.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let workflow_count = size.min(MAX_WORKFLOWS);
+
+    let categories = if rng.chance(1, 4) {
+        let count = rng.range(2..=6) as usize;
+        let len = 1 + rng.index(2);
+
+        unique_words(rng, count, len, &mut vec![])
+    } else {
+        XMAS.map(|c| c.to_string()).to_vec()
+    };
+
+    let mut unused = unique_words(rng, workflow_count - 1, 3, &mut vec!["in".to_string()]);
+    let mut queue = VecDeque::from(["in".to_string()]);
+    let mut workflows = vec![];
+
+    while let Some(name) = queue.pop_front() {
+        let mut rules = (1..rng.range(1..=4))
+            .map(|_| {
+                let category = rng.choose(&categories);
+                let operator = rng.choose(&OPERATORS);
+                let value = rng.range(1..=4000);
+
+                format!(
+                    "{category}{operator}{value}:{}",
+                    destination(rng, &mut unused, &mut queue, false)
+                )
+            })
+            .collect_vec();
+
+        // Keep the tree growing until every workflow has a place in it
+        let force = queue.is_empty();
+        rules.push(destination(rng, &mut unused, &mut queue, force));
+
+        workflows.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+
+    rng.shuffle(&mut workflows);
+
+    let parts = (0..size)
+        .map(|_| {
+            let ratings = categories
+                .iter()
+                .map(|category| format!("{category}={}", rng.range(1..=4000)))
+                .join(",");
+
+            format!("{{{ratings}}}")
+        })
+        .join("\n");
+
+    format!("{}\n\n{parts}", workflows.join("\n"))
+}
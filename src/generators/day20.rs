@@ -0,0 +1,67 @@
+use itertools::Itertools;
+
+use super::unique_words;
+use crate::rng::Rng;
+
+/// How many counters feed the conjunction in front of rx
+const COUNTERS: usize = 4;
+
+/// Four binary counters of `size` flip-flops (between 4 and 12), each counting presses up to a
+/// random period before resetting and sending a high pulse through its own feeder conjunction to
+/// the one in front of rx.
+/// The flip-flops for the period's set bits feed the counter's conjunction, which feeds back to
+/// the others and the lowest to reset them.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let bits = size.clamp(4, 12);
+
+    let mut taken = vec!["rx".to_string()];
+
+    let hub = unique_words(rng, 1, 2, &mut taken).remove(0);
+    let feeders = unique_words(rng, COUNTERS, 2, &mut taken);
+    let mut lines = vec![format!("&{hub} -> rx")];
+    let mut broadcast = vec![];
+    let mut periods = vec![];
+
+    for feeder in feeders {
+        // Odd, so the lowest bit is set, with the highest bit set so every flip-flop is used
+        let period = loop {
+            let highest = 1 << (bits - 1);
+            let period = rng.range(highest..=2 * highest - 1) | 1;
+
+            if !periods.contains(&period) {
+                break period;
+            }
+        };
+        periods.push(period);
+
+        let flip_flops = unique_words(rng, bits, 2, &mut taken);
+        let counter = unique_words(rng, 1, 2, &mut taken).remove(0);
+
+        broadcast.push(flip_flops[0].clone());
+
+        let mut counter_outputs = vec![feeder.clone(), flip_flops[0].clone()];
+
+        for (i, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs = flip_flops.get(i + 1).cloned().into_iter().collect_vec();
+
+            if period >> i & 1 == 1 {
+                outputs.push(counter.clone());
+            } else {
+                counter_outputs.push(flip_flop.clone());
+            }
+
+            rng.shuffle(&mut outputs);
+            lines.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+        }
+
+        rng.shuffle(&mut counter_outputs);
+        lines.push(format!("&{counter} -> {}", counter_outputs.join(", ")));
+        lines.push(format!("&{feeder} -> {hub}"));
+    }
+
+    rng.shuffle(&mut broadcast);
+    lines.push(format!("broadcaster -> {}", broadcast.join(", ")));
+    rng.shuffle(&mut lines);
+
+    lines.join("\n")
+}
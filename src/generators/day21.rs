@@ -0,0 +1,21 @@
+use super::grid;
+use crate::rng::Rng;
+
+/// A square garden with an odd side of at least `size` (and 5), with the start in the centre.
+/// As in the puzzle, the start's row and column and the edges are free of rocks.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let n = size.max(5) | 1;
+    let centre = n / 2;
+
+    grid(n, n, |r, c| {
+        if (r, c) == (centre, centre) {
+            'S'
+        } else if r == centre || c == centre || r == 0 || c == 0 || r == n - 1 || c == n - 1 {
+            '.'
+        } else if rng.chance(1, 6) {
+            '#'
+        } else {
+            '.'
+        }
+    })
+}
@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const N: u64 = 10;
+
+/// `size` non-overlapping bricks of up to four cubes in the 10x10 footprint, floating at heights
+/// up to twice `size` so they have to settle.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut occupied = HashSet::new();
+    let mut bricks = vec![];
+
+    while bricks.len() < size {
+        let axis = rng.index(3);
+        let len = rng.range(0..=3);
+
+        let mut start = [
+            rng.range(0..=N - 1),
+            rng.range(0..=N - 1),
+            rng.range(1..=2 * size as u64),
+        ];
+        start[axis] = start[axis]
+            .saturating_sub(len)
+            .max(if axis == 2 { 1 } else { 0 });
+
+        let mut end = start;
+        end[axis] += len;
+
+        let cubes = (0..=len)
+            .map(|i| {
+                let mut cube = start;
+                cube[axis] += i;
+                cube
+            })
+            .collect_vec();
+
+        if cubes.iter().any(|cube| occupied.contains(cube)) {
+            continue;
+        }
+
+        occupied.extend(cubes);
+        bricks.push(format!(
+            "{}~{}",
+            start.iter().join(","),
+            end.iter().join(",")
+        ));
+    }
+
+    bricks.join("\n")
+}
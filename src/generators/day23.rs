@@ -0,0 +1,142 @@
+use std::collections::VecDeque;
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+const MAX_JUNCTIONS: usize = 62;
+
+type Grid = Vec<Vec<char>>;
+
+fn open_neighbours(grid: &Grid, (r, c): (usize, usize)) -> Vec<(usize, usize)> {
+    [
+        (r.wrapping_sub(1), c),
+        (r + 1, c),
+        (r, c.wrapping_sub(1)),
+        (r, c + 1),
+    ]
+    .into_iter()
+    .filter(|&(r, c)| {
+        grid.get(r)
+            .and_then(|row| row.get(c))
+            .is_some_and(|t| *t != '#')
+    })
+    .collect()
+}
+
+fn junctions(grid: &Grid) -> Vec<(usize, usize)> {
+    (0..grid.len())
+        .cartesian_product(0..grid[0].len())
+        .filter(|&p| grid[p.0][p.1] != '#' && open_neighbours(grid, p).len() >= 3)
+        .collect()
+}
+
+/// A maze of corridors between `cells`x`cells` cells, carved as a tree and then opened up in a
+/// few places to make loops
+fn maze(rng: &mut Rng, cells: usize) -> Grid {
+    let n = 2 * cells + 1;
+    let mut grid = vec![vec!['#'; n]; n];
+    let mut stack = vec![(0usize, 0usize)];
+    let mut visited = vec![vec![false; cells]; cells];
+
+    visited[0][0] = true;
+    grid[1][1] = '.';
+
+    while let Some(&(r, c)) = stack.last() {
+        let next = [
+            (r.wrapping_sub(1), c),
+            (r + 1, c),
+            (r, c.wrapping_sub(1)),
+            (r, c + 1),
+        ]
+        .into_iter()
+        .filter(|&(r, c)| r < cells && c < cells && !visited[r][c])
+        .collect_vec();
+
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (nr, nc) = *rng.choose(&next);
+
+        visited[nr][nc] = true;
+        grid[2 * nr + 1][2 * nc + 1] = '.';
+        grid[r + nr + 1][c + nc + 1] = '.';
+        stack.push((nr, nc));
+    }
+
+    for _ in 0..cells {
+        let (r, c) = (
+            rng.range(1..=n as u64 - 2) as usize,
+            rng.range(1..=n as u64 - 2) as usize,
+        );
+
+        // Walls between two cells sit on exactly one odd coordinate
+        if (r + c) % 2 == 1 {
+            grid[r][c] = '.';
+        }
+    }
+
+    grid[0][1] = '.';
+    grid[n - 1][n - 2] = '.';
+
+    grid
+}
+
+/// A maze of 3 to `size` (at most 10) cells a side with an opening in the top and bottom rows.
+/// As in the puzzle, slopes guard every junction, pointing the way a shortest walk from the
+/// start goes, so the end can always be reached.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cells = rng.range(3..=size.clamp(3, 10) as u64) as usize;
+
+    let mut grid = loop {
+        let grid = maze(rng, cells);
+
+        if junctions(&grid).len() <= MAX_JUNCTIONS {
+            break grid;
+        }
+    };
+
+    let mut distance = vec![vec![usize::MAX; grid.len()]; grid.len()];
+    let mut queue = VecDeque::from([(0, 1)]);
+    distance[0][1] = 0;
+
+    while let Some(p) = queue.pop_front() {
+        for (r, c) in open_neighbours(&grid, p) {
+            if distance[r][c] == usize::MAX {
+                distance[r][c] = distance[p.0][p.1] + 1;
+                queue.push_back((r, c));
+            }
+        }
+    }
+
+    for junction in junctions(&grid) {
+        for next in open_neighbours(&grid, junction) {
+            if next.0 == 0 || next.0 == grid.len() - 1 {
+                continue;
+            }
+
+            // Slope from the nearer of the two to the further
+            let (from, to) = if distance[next.0][next.1] > distance[junction.0][junction.1] {
+                (junction, next)
+            } else {
+                (next, junction)
+            };
+
+            grid[next.0][next.1] = match (
+                to.0 as isize - from.0 as isize,
+                to.1 as isize - from.1 as isize,
+            ) {
+                (-1, 0) => '^',
+                (1, 0) => 'v',
+                (0, -1) => '<',
+                _ => '>',
+            };
+        }
+    }
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .join("\n")
+}
@@ -0,0 +1,42 @@
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+/// `size` hailstones (at least three) around the puzzle's test area, all hit by one rock thrown
+/// from whole-number coordinates at some whole-number time.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let position = [0; 3].map(|_| rng.signed(150_000_000_000_000..=450_000_000_000_000));
+    let velocity = [0; 3].map(|_| rng.signed(-300..=300));
+
+    let mut times = vec![];
+
+    (0..size.max(3))
+        .map(|_| {
+            let time = loop {
+                let time = rng.signed(1_000_000_000..=1_000_000_000_000);
+
+                if !times.contains(&time) {
+                    times.push(time);
+                    break time;
+                }
+            };
+
+            let hail_velocity = loop {
+                let v = [0; 3].map(|_| rng.signed(-300..=300));
+
+                if v != velocity {
+                    break v;
+                }
+            };
+
+            let hail_position =
+                [0, 1, 2].map(|i| position[i] + (velocity[i] - hail_velocity[i]) * time);
+
+            format!(
+                "{} @ {}",
+                hail_position.iter().join(", "),
+                hail_velocity.iter().join(", ")
+            )
+        })
+        .join("\n")
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use super::unique_words;
+use crate::rng::Rng;
+
+/// Connects every component to the next two round a ring, which takes cutting four wires to split,
+/// plus a few random wires
+fn cluster(rng: &mut Rng, names: &[String], edges: &mut Vec<(String, String)>) {
+    let n = names.len();
+
+    for i in 0..n {
+        edges.push((names[i].clone(), names[(i + 1) % n].clone()));
+        edges.push((names[i].clone(), names[(i + 2) % n].clone()));
+    }
+
+    for _ in 0..n / 2 {
+        let (a, b) = (rng.index(n), rng.index(n));
+
+        if a != b {
+            edges.push((names[a].clone(), names[b].clone()));
+        }
+    }
+}
+
+/// Two groups of 5 to `size` components, each too well connected to split with three cuts,
+/// joined by exactly three wires.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max = size.max(5) as u64;
+    let (left, right) = (rng.range(5..=max) as usize, rng.range(5..=max) as usize);
+
+    let names = unique_words(rng, left + right, 3, &mut vec![]);
+    let (left, right) = names.split_at(left);
+
+    let mut edges = vec![];
+
+    cluster(rng, left, &mut edges);
+    cluster(rng, right, &mut edges);
+
+    let mut left = left.to_vec();
+    let mut right = right.to_vec();
+    rng.shuffle(&mut left);
+    rng.shuffle(&mut right);
+
+    for i in 0..3 {
+        edges.push((left[i].clone(), right[i].clone()));
+    }
+
+    // Each wire is listed once, under either of its ends
+    let mut connections: HashMap<String, Vec<String>> = HashMap::new();
+
+    let edges = edges
+        .into_iter()
+        .map(|(a, b)| if a < b { (a, b) } else { (b, a) })
+        .unique();
+
+    for (a, b) in edges {
+        let (from, to) = if rng.chance(1, 2) { (a, b) } else { (b, a) };
+
+        connections.entry(from).or_default().push(to);
+    }
+
+    let mut lines = connections
+        .into_iter()
+        .sorted()
+        .map(|(from, to)| format!("{from}: {}", to.join(" ")))
+        .collect_vec();
+
+    rng.shuffle(&mut lines);
+
+    lines.join("\n")
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+pub const HISTORY_PATH: &str = "./history.txt";
+
+/// Puzzles unlock at midnight EST (05:00 UTC), starting 2023-12-01.
+const FIRST_UNLOCK: u64 = 1701406800;
+
+pub fn unlock_time(day: u8) -> u64 {
+    FIRST_UNLOCK + (day as u64 - 1) * 24 * 60 * 60
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Verdict {
+    Correct,
+    TooLow,
+    TooHigh,
+    Wrong,
+    TooSoon,
+    AlreadySolved,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::TooLow => "too-low",
+            Verdict::TooHigh => "too-high",
+            Verdict::Wrong => "wrong",
+            Verdict::TooSoon => "too-soon",
+            Verdict::AlreadySolved => "already-solved",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-low" => Ok(Verdict::TooLow),
+            "too-high" => Ok(Verdict::TooHigh),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-soon" => Ok(Verdict::TooSoon),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            _ => Err(format!("Unknown verdict: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub day: u8,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} {}",
+            self.timestamp, self.day, self.part, self.verdict, self.answer
+        )
+    }
+}
+
+impl FromStr for Submission {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<_> = line.split_whitespace().collect();
+
+        let [timestamp, day, part, verdict, answer] = fields[..] else {
+            return Err(format!("Expected 5 fields: {line}"));
+        };
+
+        Ok(Submission {
+            timestamp: timestamp.parse().map_err(|_| "Bad timestamp")?,
+            day: day.parse().map_err(|_| "Bad day")?,
+            part: part.parse().map_err(|_| "Bad part")?,
+            verdict: verdict.parse()?,
+            answer: answer.to_string(),
+        })
+    }
+}
+
+/// Every answer submitted through `aoc submit`, oldest first.
+#[derive(Debug, Default, Clone)]
+pub struct History(Vec<Submission>);
+
+impl History {
+    /// A missing file is treated as an empty history; malformed lines are skipped.
+    pub fn load(path: impl AsRef<Path>) -> History {
+        let Ok(file) = fs::read_to_string(path) else {
+            return History::default();
+        };
+
+        History(file.lines().filter_map(|line| line.parse().ok()).collect())
+    }
+
+    pub fn append(path: impl AsRef<Path>, submission: &Submission) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        writeln!(file, "{submission}")
+    }
+
+    pub fn first_correct(&self, day: u8, part: u8) -> Option<&Submission> {
+        self.0
+            .iter()
+            .find(|s| s.day == day && s.part == part && s.verdict == Verdict::Correct)
+    }
+
+    /// Seconds between the puzzle unlocking and the first correct submission.
+    pub fn solve_time(&self, day: u8, part: u8) -> Option<u64> {
+        self.first_correct(day, part)
+            .map(|s| s.timestamp.saturating_sub(unlock_time(day)))
+    }
+}
+
+pub fn format_duration(secs: u64) -> String {
+    let (days, secs) = (secs / 86400, secs % 86400);
+    let hms = format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {hms}")
+    } else {
+        hms
+    }
+}
@@ -0,0 +1,181 @@
+//! Writes pictures of puzzle states as PPM or PNG, drawing grids a block of pixels per cell.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::days::*;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Draws a `rows`x`cols` grid as `scale`x`scale` blocks of pixels, coloured by `colour(row, col)`.
+    pub fn from_grid(
+        rows: usize,
+        cols: usize,
+        scale: usize,
+        colour: impl Fn(usize, usize) -> Rgb,
+    ) -> Image {
+        let mut image = Image::new(cols * scale, rows * scale, BLACK);
+
+        for (row, col) in (0..rows).flat_map(|r| (0..cols).map(move |c| (r, c))) {
+            let rgb = colour(row, col);
+
+            for y in row * scale..(row + 1) * scale {
+                for x in col * scale..(col + 1) * scale {
+                    image.set(x, y, rgb);
+                }
+            }
+        }
+
+        image
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, rgb: Rgb) {
+        self.pixels[y * self.width + x] = rgb;
+    }
+
+    pub fn ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+
+        bytes.extend(self.pixels.iter().flatten());
+        bytes
+    }
+
+    /// An uncompressed PNG: the pixel rows go into stored deflate blocks, so no compressor is
+    /// needed and any viewer can still open it.
+    pub fn png(&self) -> Vec<u8> {
+        // Each row starts with filter type 0, leaving its bytes as they are
+        let raw: Vec<u8> = self
+            .pixels
+            .chunks(self.width.max(1))
+            .flat_map(|row| [0].into_iter().chain(row.iter().flatten().copied()))
+            .collect();
+
+        let mut zlib = vec![0x78, 0x01];
+        let blocks = raw.chunks(u16::MAX as usize).collect::<Vec<_>>();
+
+        for (i, block) in blocks.iter().enumerate() {
+            let len = block.len() as u16;
+
+            zlib.push((i == blocks.len() - 1) as u8);
+            zlib.extend(len.to_le_bytes());
+            zlib.extend((!len).to_le_bytes());
+            zlib.extend(*block);
+        }
+
+        if blocks.is_empty() {
+            zlib.extend([1, 0, 0, 0xff, 0xff]);
+        }
+
+        zlib.extend(adler32(&raw).to_be_bytes());
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits a channel, RGB, default compression, filtering and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        chunk(&mut png, b"IHDR", &header);
+        chunk(&mut png, b"IDAT", &zlib);
+        chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    /// Writes a PNG if the path ends in `.png`, and a PPM otherwise.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if path.extension().is_some_and(|ext| ext == "png") {
+            fs::write(path, self.png())
+        } else {
+            fs::write(path, self.ppm())
+        }
+    }
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(&[&kind[..], data].concat()).to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for byte in bytes {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for byte in bytes {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+/// Parses a colour written as `rrggbb`, with or without a leading `#`.
+pub fn hex(s: &str) -> Option<Rgb> {
+    let s = s.trim_start_matches('#');
+
+    if s.len() != 6 {
+        return None;
+    }
+
+    let channel = |i: usize| u8::from_str_radix(s.get(i..i + 2)?, 16).ok();
+
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Black through red and yellow to white as `t` goes from 0 to 1.
+pub fn heat(t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0) * 3.0;
+    let channel = |from: f64| ((t - from).clamp(0.0, 1.0) * 255.0) as u8;
+
+    [channel(0.0), channel(1.0), channel(2.0)]
+}
+
+/// Parses the input and draws a picture of it
+pub type Render = fn(&str) -> Image;
+
+pub fn renderer(day: u8) -> Option<Render> {
+    Some(match day {
+        10 => day10::render,
+        16 => day16::render,
+        17 => day17::render,
+        18 => day18::render,
+        _ => return None,
+    })
+}
@@ -0,0 +1,149 @@
+use std::any::type_name;
+use std::str::FromStr;
+
+use itertools::Itertools;
+use regex::Regex;
+
+use crate::days::*;
+
+/// An assumption a solution makes about the shape of its input.
+pub struct Invariant {
+    pub description: &'static str,
+    pub check: fn(&str) -> Result<(), String>,
+}
+
+pub struct Violation {
+    pub description: &'static str,
+    pub reason: String,
+}
+
+pub fn violations(input: &str, invariants: &[Invariant]) -> Vec<Violation> {
+    invariants
+        .iter()
+        .filter_map(|invariant| {
+            (invariant.check)(input).err().map(|reason| Violation {
+                description: invariant.description,
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Describes what in the input the solution copes with but is likely a mistake, such as parts of
+/// it nothing uses
+pub type Warnings = fn(&str) -> Vec<String>;
+
+pub fn warnings_for(day: u8) -> Option<Warnings> {
+    Some(match day {
+        19 => day19::warnings,
+        _ => return None,
+    })
+}
+
+/// Lists every violated invariant as the error.
+pub fn check(input: &str, invariants: &[Invariant]) -> Result<(), String> {
+    let violations = violations(input, invariants);
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "Input violates the solution's assumptions:\n{}",
+        violations
+            .iter()
+            .map(|v| format!("  - {}: {}", v.description, v.reason))
+            .join("\n")
+    ))
+}
+
+/// Panics with every violated invariant, rather than letting the solution misbehave on bad input.
+pub fn ensure(input: &str, invariants: &[Invariant]) {
+    if let Err(message) = check(input, invariants) {
+        panic!("{message}");
+    }
+}
+
+/// Lines as the parsers see them, so a blank or padded line at the end is still checked
+fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(i, l)| (i + 1, l))
+}
+
+/// Runs `check` on every line, reporting the first failure with its line number.
+pub fn each_line(input: &str, check: impl Fn(&str) -> Result<(), String>) -> Result<(), String> {
+    lines(input).try_for_each(|(n, line)| check(line).map_err(|e| format!("line {n}: {e}")))
+}
+
+pub fn lines_match(input: &str, pattern: &str) -> Result<(), String> {
+    let re = Regex::new(pattern).unwrap();
+
+    each_line(input, |line| {
+        if re.is_match(line) {
+            Ok(())
+        } else {
+            Err(format!("{line:?} does not match {pattern}"))
+        }
+    })
+}
+
+pub fn grid_size(input: &str, rows: usize, cols: usize) -> Result<(), String> {
+    let count = lines(input).count();
+
+    if count != rows {
+        return Err(format!("expected {rows} rows, found {count}"));
+    }
+
+    each_line(input, |line| {
+        let len = line.chars().count();
+
+        if len == cols {
+            Ok(())
+        } else {
+            Err(format!("expected {cols} columns, found {len}"))
+        }
+    })
+}
+
+pub fn rectangular(input: &str) -> Result<(), String> {
+    let cols = input.lines().next().map_or(0, |l| l.chars().count());
+
+    grid_size(input, lines(input).count(), cols)
+}
+
+pub fn grid_chars(input: &str, allowed: &str) -> Result<(), String> {
+    each_line(input, |line| {
+        match line.chars().find(|c| !allowed.contains(*c)) {
+            Some(c) => Err(format!("unexpected {c:?}, expected one of {allowed:?}")),
+            None => Ok(()),
+        }
+    })
+}
+
+pub fn count_char(input: &str, c: char, expected: usize) -> Result<(), String> {
+    let count = input.chars().filter(|x| *x == c).count();
+
+    if count == expected {
+        Ok(())
+    } else {
+        Err(format!("expected {expected} {c:?}, found {count}"))
+    }
+}
+
+/// Every run of digits, with any leading minus, parses as a `T` rather than overflowing it.
+pub fn numbers_fit<T: FromStr>(input: &str) -> Result<(), String> {
+    let re = Regex::new(r"-?\d+").unwrap();
+
+    each_line(input, |line| {
+        match re
+            .find_iter(line)
+            .find(|n| n.as_str().parse::<T>().is_err())
+        {
+            Some(n) => Err(format!(
+                "{} does not fit in {}",
+                n.as_str(),
+                type_name::<T>()
+            )),
+            None => Ok(()),
+        }
+    })
+}
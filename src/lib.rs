@@ -0,0 +1,21 @@
+// Lets the day solutions refer to the library by name whether built as a binary or as `days`
+extern crate self as advent_of_code_2023;
+
+pub mod allocator;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod days;
+pub mod dot;
+pub mod generators;
+pub mod history;
+pub mod image;
+pub mod invariants;
+pub mod properties;
+pub mod registry;
+pub mod rng;
+pub mod stats;
+pub mod svg;
+pub mod vcd;
+pub mod visual;
@@ -0,0 +1,38 @@
+use std::fmt::{Debug, Display};
+
+use crate::days::*;
+use crate::rng::Rng;
+
+/// A claim that an optimised function agrees with a slow but obviously correct reference, checked
+/// on small cases it generates.
+pub struct Property {
+    pub description: &'static str,
+    pub check: fn(&mut Rng) -> Result<(), String>,
+}
+
+/// Compares the two results, describing the case they differ on.
+pub fn agree<T: PartialEq + Debug>(
+    case: impl Display,
+    optimised: T,
+    reference: T,
+) -> Result<(), String> {
+    if optimised == reference {
+        Ok(())
+    } else {
+        Err(format!(
+            "{case}: optimised gave {optimised:?}, reference gave {reference:?}"
+        ))
+    }
+}
+
+pub fn properties_for(day: u8) -> &'static [Property] {
+    match day {
+        5 => day05::PROPERTIES,
+        6 => day06::PROPERTIES,
+        11 => day11::PROPERTIES,
+        12 => day12::PROPERTIES,
+        19 => day19::PROPERTIES,
+        20 => day20::PROPERTIES,
+        _ => &[],
+    }
+}
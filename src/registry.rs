@@ -0,0 +1,110 @@
+use crate::answer::Answer;
+use crate::days::*;
+use crate::invariants::{self, Invariant};
+
+/// Solves the given input, passing each part's answer to the callback as soon as it is known
+pub type Run = fn(&str, &mut dyn FnMut(Answer));
+
+/// Parses the given input and discards it, to exercise the parser alone
+pub type Parse = fn(&str);
+
+pub struct Solution {
+    pub day: u8,
+    pub bin: &'static str,
+    pub parts: u8,
+    /// Both parts are solved in one pass, so only their total time and peak heap are known
+    pub combined: bool,
+    pub invariants: &'static [Invariant],
+    pub parser: Parse,
+    pub run: Run,
+}
+
+impl Solution {
+    /// Alternative implementations share the input of their day
+    pub fn input_path(&self) -> String {
+        format!("./src/bin/day{:02}/input.txt", self.day)
+    }
+
+    pub fn example_path(&self) -> String {
+        format!("./src/bin/day{:02}/ex.txt", self.day)
+    }
+
+    /// Solves the day's example with the smaller parameters the puzzle uses for it, on the days
+    /// that need them. These are written for the example, which may break the input's invariants.
+    pub fn example_run(&self) -> Option<Run> {
+        Some(match self.day {
+            11 => day11::run_example,
+            21 => day21::run_example,
+            24 => day24::run_example,
+            _ => return None,
+        })
+    }
+
+    pub fn source_dir(&self) -> String {
+        format!("./src/bin/{}", self.bin)
+    }
+
+    /// Rejects input that breaks the solution's assumptions, which the parser relies on, before
+    /// parsing the rest.
+    pub fn parse(&self, input: &str) -> Result<(), String> {
+        invariants::check(input, self.invariants)?;
+
+        (self.parser)(input);
+
+        Ok(())
+    }
+}
+
+/// The solution in the `days` module `$module`, reading its part count and whether they are
+/// combined from its `PARTS`; its binary is named after the module unless given.
+macro_rules! solution {
+    ($day:expr, $module:ident) => {
+        solution!($day, $module, stringify!($module))
+    };
+    ($day:expr, $module:ident, $bin:expr) => {
+        Solution {
+            day: $day,
+            bin: $bin,
+            parts: $module::PARTS.count(),
+            combined: $module::PARTS.combined(),
+            invariants: $module::INVARIANTS,
+            parser: |file| {
+                $module::parse(file);
+            },
+            run: $module::run,
+        }
+    };
+}
+
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(1, day01),
+    solution!(2, day02),
+    solution!(3, day03),
+    solution!(4, day04),
+    solution!(5, day05),
+    solution!(6, day06),
+    solution!(7, day07),
+    solution!(8, day08),
+    solution!(9, day09),
+    solution!(10, day10),
+    solution!(11, day11),
+    solution!(12, day12),
+    solution!(13, day13),
+    solution!(14, day14),
+    solution!(15, day15),
+    solution!(16, day16),
+    solution!(17, day17),
+    solution!(17, day17_new, "day17-new"),
+    solution!(18, day18),
+    solution!(19, day19),
+    solution!(20, day20),
+    solution!(21, day21),
+    solution!(22, day22),
+    solution!(23, day23),
+    solution!(24, day24),
+    solution!(25, day25),
+];
+
+pub fn solutions_for(day: u8) -> impl Iterator<Item = &'static Solution> {
+    SOLUTIONS.iter().filter(move |s| s.day == day)
+}
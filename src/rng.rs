@@ -0,0 +1,70 @@
+use std::ops::RangeInclusive;
+
+/// A small seeded generator (SplitMix64), so generated inputs can be reproduced from their seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 to `width`, which may be `u64::MAX`
+    fn offset(&mut self, width: u64) -> u64 {
+        match width.checked_add(1) {
+            Some(len) => self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+
+        assert!(
+            start <= end,
+            "Cannot pick from the empty range {start}..={end}"
+        );
+
+        start + self.offset(end - start)
+    }
+
+    pub fn signed(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+
+        assert!(
+            start <= end,
+            "Cannot pick from the empty range {start}..={end}"
+        );
+
+        start.wrapping_add(self.offset(end.abs_diff(start)) as i64)
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "Cannot pick an index into nothing");
+
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.next_u64() % denominator < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Collects the tracing spans around parsing and each part, with the time spent in them and the
+//! counters days report as events, such as how many states a search popped.
+//!
+//! Days emit counters once they have them, as `tracing::info!(popped, pushed)`, so counting costs
+//! nothing more than a local variable. Numeric fields add up within the span they happen in.
+//!
+//! Warnings, such as a day falling back to a slower method, are kept whole for the user to read.
+
+use std::fmt::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// What happened inside one span
+#[derive(Clone, Debug, Default)]
+pub struct SpanStats {
+    pub name: String,
+    /// How many spans it is nested in
+    pub depth: usize,
+    pub elapsed: Duration,
+    pub counters: Vec<(String, u64)>,
+    /// The messages of warnings and errors, with any other fields
+    pub warnings: Vec<String>,
+}
+
+impl SpanStats {
+    fn add(&mut self, counter: &str, n: u64) {
+        match self.counters.iter_mut().find(|(name, _)| name == counter) {
+            Some((_, total)) => *total += n,
+            None => self.counters.push((counter.to_string(), n)),
+        }
+    }
+}
+
+impl fmt::Display for SpanStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} took {:.2?}", self.name, self.elapsed)?;
+
+        for (i, (name, n)) in self.counters.iter().enumerate() {
+            write!(f, "{} {name} {n}", if i == 0 { ":" } else { "," })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct State {
+    spans: Vec<SpanStats>,
+    entered: Vec<(usize, Instant)>,
+}
+
+/// A subscriber keeping [`SpanStats`] for every span, in the order they were created.
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<State>>);
+
+struct Counters<'a>(&'a mut SpanStats);
+
+impl Visit for Counters<'_> {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.add(field.name(), value);
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.add(field.name(), value.max(0) as u64);
+    }
+
+    /// Messages and other fields aren't counters
+    fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+}
+
+/// A warning's message followed by its other fields as `name=value`
+#[derive(Default)]
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if !self.0.is_empty() {
+            self.0.push(' ');
+        }
+
+        match field.name() {
+            "message" => write!(self.0, "{value:?}"),
+            name => write!(self.0, "{name}={value:?}"),
+        }
+        .unwrap();
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record_debug(field, &format_args!("{value}"));
+    }
+}
+
+fn index(id: &Id) -> usize {
+    id.into_u64() as usize - 1
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes) -> Id {
+        let mut state = self.0.lock().unwrap();
+        let mut stats = SpanStats {
+            name: span.metadata().name().to_string(),
+            depth: state.entered.len(),
+            ..SpanStats::default()
+        };
+
+        span.record(&mut Counters(&mut stats));
+        state.spans.push(stats);
+
+        Id::from_u64(state.spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record) {
+        let mut state = self.0.lock().unwrap();
+
+        values.record(&mut Counters(&mut state.spans[index(span)]));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event) {
+        let mut state = self.0.lock().unwrap();
+
+        if let Some(&(i, _)) = state.entered.last() {
+            if *event.metadata().level() <= Level::WARN {
+                let mut message = Message::default();
+
+                event.record(&mut message);
+                state.spans[i].warnings.push(message.0);
+            } else {
+                event.record(&mut Counters(&mut state.spans[i]));
+            }
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        self.0
+            .lock()
+            .unwrap()
+            .entered
+            .push((index(span), Instant::now()));
+    }
+
+    fn exit(&self, span: &Id) {
+        let mut state = self.0.lock().unwrap();
+
+        if let Some(at) = state.entered.iter().rposition(|(i, _)| *i == index(span)) {
+            let (i, since) = state.entered.remove(at);
+
+            state.spans[i].elapsed += since.elapsed();
+        }
+    }
+}
+
+/// Runs `f`, returning the stats of every span it created.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Vec<SpanStats>) {
+    let collector = Collector::default();
+    let result = tracing::subscriber::with_default(collector.clone(), f);
+    let spans = std::mem::take(&mut collector.0.lock().unwrap().spans);
+
+    (result, spans)
+}
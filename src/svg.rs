@@ -0,0 +1,113 @@
+//! Builds SVG documents for puzzle states that are shapes rather than grids.
+
+use std::fmt::Write;
+
+use crate::days::*;
+
+pub struct Svg {
+    width: f64,
+    height: f64,
+    body: String,
+}
+
+/// Escapes text for use in element content and attribute values
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Svg {
+    pub fn new(width: f64, height: f64) -> Svg {
+        Svg {
+            width,
+            height,
+            body: String::new(),
+        }
+    }
+
+    pub fn rect(&mut self, (x, y): (f64, f64), (width, height): (f64, f64), fill: &str) {
+        writeln!(
+            self.body,
+            r#"  <rect x="{x:.2}" y="{y:.2}" width="{width:.2}" height="{height:.2}" fill="{}"/>"#,
+            escape(fill)
+        )
+        .unwrap();
+    }
+
+    pub fn line(&mut self, (x1, y1): (f64, f64), (x2, y2): (f64, f64), stroke: &str, width: f64) {
+        writeln!(
+            self.body,
+            r#"  <line x1="{x1:.2}" y1="{y1:.2}" x2="{x2:.2}" y2="{y2:.2}" stroke="{}" stroke-width="{width}" stroke-linecap="square"/>"#,
+            escape(stroke)
+        )
+        .unwrap();
+    }
+
+    pub fn polygon(&mut self, points: &[(f64, f64)], stroke: &str, fill: &str, width: f64) {
+        let points = points
+            .iter()
+            .map(|(x, y)| format!("{x:.2},{y:.2}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(
+            self.body,
+            r#"  <polygon points="{points}" stroke="{}" fill="{}" stroke-width="{width}" stroke-linejoin="miter"/>"#,
+            escape(stroke),
+            escape(fill)
+        )
+        .unwrap();
+    }
+
+    pub fn text(&mut self, (x, y): (f64, f64), size: f64, fill: &str, text: &str) {
+        writeln!(
+            self.body,
+            r#"  <text x="{x:.2}" y="{y:.2}" font-family="monospace" font-size="{size}" fill="{}">{}</text>"#,
+            escape(fill),
+            escape(text)
+        )
+        .unwrap();
+    }
+
+    pub fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n{}</svg>\n",
+            self.body,
+            w = self.width,
+            h = self.height
+        )
+    }
+}
+
+/// Scales points into a `size`x`size` box at `origin`, keeping their aspect ratio, and flips them
+/// so that positive y points up.
+pub fn fit(points: &[(f64, f64)], origin: (f64, f64), size: f64) -> Vec<(f64, f64)> {
+    let min_x = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+    let max_x = points.iter().map(|p| p.0).fold(f64::NEG_INFINITY, f64::max);
+    let min_y = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let max_y = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+
+    let scale = size / (max_x - min_x).max(max_y - min_y).max(1.0);
+
+    points
+        .iter()
+        .map(|(x, y)| {
+            (
+                origin.0 + (x - min_x) * scale,
+                origin.1 + (max_y - y) * scale,
+            )
+        })
+        .collect()
+}
+
+/// Parses the input and draws it as an SVG document
+pub type RenderSvg = fn(&str) -> String;
+
+pub fn svg_renderer(day: u8) -> Option<RenderSvg> {
+    Some(match day {
+        18 => day18::svg,
+        _ => return None,
+    })
+}
@@ -0,0 +1,146 @@
+//! Writes Value Change Dump files, which waveform viewers such as GTKWave open, to watch
+//! simulated signals change over time.
+
+use std::fmt::Write;
+
+use crate::days::*;
+
+/// A signal declared with [`Vcd::wire`]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Signal(usize);
+
+pub struct Vcd {
+    scope: String,
+    wires: Vec<(String, u32)>,
+    values: Vec<Option<u64>>,
+    time: Option<u64>,
+    body: String,
+}
+
+/// The short code a signal goes by in the dump, counting in base 94 over printable ASCII
+fn code(Signal(mut i): Signal) -> String {
+    let mut code = String::new();
+
+    loop {
+        code.push((b'!' + (i % 94) as u8) as char);
+        i /= 94;
+
+        if i == 0 {
+            return code;
+        }
+
+        i -= 1;
+    }
+}
+
+impl Vcd {
+    /// A dump whose signals all sit in one module scope
+    pub fn new(scope: &str) -> Vcd {
+        Vcd {
+            scope: scope.to_string(),
+            wires: vec![],
+            values: vec![],
+            time: None,
+            body: String::new(),
+        }
+    }
+
+    /// Declares a signal `width` bits wide; all of them must be declared before the first change.
+    pub fn wire(&mut self, name: &str, width: u32) -> Signal {
+        assert!(
+            self.time.is_none(),
+            "Signals must be declared before changes"
+        );
+
+        self.wires.push((name.to_string(), width));
+        self.values.push(None);
+
+        Signal(self.wires.len() - 1)
+    }
+
+    /// Records a signal's value at `time`, which must not go backwards. Values that don't change
+    /// are left out of the dump.
+    pub fn change(&mut self, time: u64, signal: Signal, value: u64) {
+        if self.values[signal.0] == Some(value) {
+            return;
+        }
+
+        if self.time != Some(time) {
+            assert!(self.time < Some(time), "Time went backwards");
+
+            self.time = Some(time);
+            writeln!(self.body, "#{time}").unwrap();
+        }
+
+        self.values[signal.0] = Some(value);
+
+        match self.wires[signal.0].1 {
+            1 => writeln!(self.body, "{}{}", value & 1, code(signal)),
+            _ => writeln!(self.body, "b{value:b} {}", code(signal)),
+        }
+        .unwrap();
+    }
+
+    pub fn finish(self) -> String {
+        let mut header = String::new();
+
+        writeln!(header, "$timescale 1ns $end").unwrap();
+        writeln!(header, "$scope module {} $end", self.scope).unwrap();
+
+        for (i, (name, width)) in self.wires.iter().enumerate() {
+            let kind = if *width == 1 { "wire" } else { "integer" };
+
+            writeln!(
+                header,
+                "$var {kind} {width} {} {name} $end",
+                code(Signal(i))
+            )
+            .unwrap();
+        }
+
+        writeln!(header, "$upscope $end").unwrap();
+        writeln!(header, "$enddefinitions $end").unwrap();
+
+        header + &self.body
+    }
+}
+
+/// Parses the input, runs its simulation for the given number of steps and dumps the named
+/// signals, or all of them when none are named
+pub type RecordVcd = fn(&str, usize, &[String]) -> Result<String, String>;
+
+pub fn vcd_recorder(day: u8) -> Option<RecordVcd> {
+    Some(match day {
+        20 => day20::vcd,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_signal_trace() {
+        let mut vcd = Vcd::new("top");
+        let clk = vcd.wire("clk", 1);
+        let count = vcd.wire("count", 8);
+
+        vcd.change(0, clk, 1);
+        vcd.change(0, count, 5);
+        vcd.change(1, clk, 1);
+
+        assert_eq!(
+            vcd.finish(),
+            "$timescale 1ns $end\n\
+             $scope module top $end\n\
+             $var wire 1 ! clk $end\n\
+             $var integer 8 \" count $end\n\
+             $upscope $end\n\
+             $enddefinitions $end\n\
+             #0\n\
+             1!\n\
+             b101 \"\n"
+        );
+    }
+}
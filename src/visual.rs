@@ -0,0 +1,216 @@
+//! Draws grids in the terminal with ANSI colours, and animates simulations over them frame by
+//! frame, for watching what a solution does rather than adding `println!`s to it.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::days::*;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    fn foreground(self) -> u8 {
+        match self {
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 37,
+            Colour::Grey => 90,
+        }
+    }
+
+    fn background(self) -> u8 {
+        self.foreground() + 10
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct Cell {
+    c: char,
+    fg: Option<Colour>,
+    bg: Option<Colour>,
+}
+
+/// Characters laid out in rows, each with an optional colour and background.
+#[derive(Clone)]
+pub struct Grid {
+    cols: usize,
+    cells: Vec<Cell>,
+}
+
+impl Grid {
+    pub fn new(rows: usize, cols: usize) -> Grid {
+        let blank = Cell {
+            c: ' ',
+            fg: None,
+            bg: None,
+        };
+
+        Grid {
+            cols,
+            cells: vec![blank; rows * cols],
+        }
+    }
+
+    /// A grid as wide as its longest row, with shorter rows padded with spaces.
+    pub fn from_rows<R: IntoIterator<Item = char>>(rows: impl IntoIterator<Item = R>) -> Grid {
+        let rows: Vec<Vec<char>> = rows.into_iter().map(|r| r.into_iter().collect()).collect();
+        let cols = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+        let mut grid = Grid::new(rows.len(), cols);
+
+        for (r, row) in rows.iter().enumerate() {
+            for (c, ch) in row.iter().enumerate() {
+                grid.set(r, c, *ch);
+            }
+        }
+
+        grid
+    }
+
+    pub fn rows(&self) -> usize {
+        self.cells.len().checked_div(self.cols).unwrap_or(0)
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    fn cell(&mut self, row: usize, col: usize) -> &mut Cell {
+        &mut self.cells[row * self.cols + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, c: char) {
+        self.cell(row, col).c = c;
+    }
+
+    pub fn colour(&mut self, row: usize, col: usize, colour: Colour) {
+        self.cell(row, col).fg = Some(colour);
+    }
+
+    pub fn highlight(&mut self, row: usize, col: usize, colour: Colour) {
+        self.cell(row, col).bg = Some(colour);
+    }
+
+    /// Colours every given cell's background, as a layer over what the grid already shows.
+    pub fn overlay(&mut self, cells: impl IntoIterator<Item = (usize, usize)>, colour: Colour) {
+        for (row, col) in cells {
+            self.highlight(row, col, colour);
+        }
+    }
+}
+
+impl fmt::Display for Grid {
+    /// Only switches colour where it changes along a row, to keep large grids quick to print.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.cols.max(1)) {
+            let mut style = (None, None);
+
+            for cell in row {
+                if (cell.fg, cell.bg) != style {
+                    style = (cell.fg, cell.bg);
+
+                    write!(f, "\x1b[0")?;
+
+                    if let Some(fg) = cell.fg {
+                        write!(f, ";{}", fg.foreground())?;
+                    }
+
+                    if let Some(bg) = cell.bg {
+                        write!(f, ";{}", bg.background())?;
+                    }
+
+                    write!(f, "m")?;
+                }
+
+                write!(f, "{}", cell.c)?;
+            }
+
+            writeln!(f, "\x1b[0m")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Redraws frames in place, either at a fixed rate or waiting for Enter between them.
+pub struct Animation {
+    delay: Option<Duration>,
+    every: usize,
+    frames: usize,
+}
+
+impl Animation {
+    /// Shows `fps` frames a second, or steps a frame at a time when `fps` is 0.
+    pub fn new(fps: f64) -> Animation {
+        Animation {
+            delay: (fps > 0.0).then(|| Duration::from_secs_f64(1.0 / fps)),
+            every: 1,
+            frames: 0,
+        }
+    }
+
+    /// Only draws every `n`th frame, to get through long simulations.
+    pub fn every(mut self, n: usize) -> Animation {
+        self.every = n.max(1);
+        self
+    }
+
+    pub fn frame(&mut self, title: &str, grid: &Grid) {
+        self.frames += 1;
+
+        if (self.frames - 1).is_multiple_of(self.every) {
+            self.draw(title, grid);
+            self.wait();
+        }
+    }
+
+    /// Draws the last frame whether or not it would have been skipped, without waiting after it.
+    pub fn finish(&mut self, title: &str, grid: &Grid) {
+        self.draw(title, grid);
+    }
+
+    fn draw(&self, title: &str, grid: &Grid) {
+        let mut stdout = io::stdout().lock();
+
+        // Home the cursor and clear the screen, so each frame replaces the last
+        write!(stdout, "\x1b[H\x1b[2J{title}\n{grid}").unwrap();
+        stdout.flush().unwrap();
+    }
+
+    fn wait(&self) {
+        match self.delay {
+            Some(delay) => thread::sleep(delay),
+            None => {
+                io::stdin().read_line(&mut String::new()).unwrap();
+            }
+        }
+    }
+}
+
+/// Parses the input and animates the day's simulation over it
+pub type Visualise = fn(&str, &mut Animation);
+
+pub fn visualiser(day: u8) -> Option<Visualise> {
+    Some(match day {
+        10 => day10::visualise,
+        14 => day14::visualise,
+        16 => day16::visualise,
+        17 => day17::visualise,
+        _ => return None,
+    })
+}
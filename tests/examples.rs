@@ -0,0 +1,62 @@
+//! Solves every day's example that its solution can take and checks the answers the puzzle gives
+//! for it, as `aoc watch` does. Examples that break their day's invariants are skipped, and mustn't
+//! have answers that would go unchecked.
+
+use std::fs;
+use std::path::Path;
+
+use advent_of_code_2023::answers::{Answers, EXAMPLE_ANSWERS_PATH};
+use advent_of_code_2023::invariants::violations;
+use advent_of_code_2023::registry::SOLUTIONS;
+
+#[test]
+fn examples_give_the_puzzles_answers() {
+    let expected = Answers::load(EXAMPLE_ANSWERS_PATH);
+    let mut failures = vec![];
+
+    for solution in SOLUTIONS {
+        let path = solution.example_path();
+
+        if !Path::new(&path).exists() {
+            continue;
+        }
+
+        let input = fs::read_to_string(&path).unwrap();
+
+        let run = match solution.example_run() {
+            Some(run) => run,
+            None if violations(&input, solution.invariants).is_empty() => solution.run,
+            None => {
+                eprintln!(
+                    "{}: skipped, the example breaks its invariants",
+                    solution.bin
+                );
+
+                if (1..=2).any(|part| expected.get(solution.day, part).is_some()) {
+                    failures.push(format!(
+                        "{} has example answers, but its example breaks its invariants",
+                        solution.bin
+                    ));
+                }
+
+                continue;
+            }
+        };
+
+        let mut part = 0;
+
+        run(&input, &mut |answer| {
+            part += 1;
+
+            match expected.get(solution.day, part) {
+                Some(expected) if *expected != answer => failures.push(format!(
+                    "{} part {part}: expected {expected}, got {answer}",
+                    solution.bin
+                )),
+                _ => {}
+            }
+        });
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
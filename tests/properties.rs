@@ -0,0 +1,28 @@
+//! Runs every day's properties on the first cases `aoc props` tries, always with the same seeds,
+//! so `cargo test` catches an optimised function drifting from its reference.
+
+use advent_of_code_2023::properties::properties_for;
+use advent_of_code_2023::rng::Rng;
+
+const CASES: u64 = 100;
+
+#[test]
+fn properties_hold() {
+    let mut failures = vec![];
+
+    for day in 1..=25 {
+        for property in properties_for(day) {
+            let counterexample = (0..CASES)
+                .find_map(|case| Some((case, (property.check)(&mut Rng::new(case)).err()?)));
+
+            if let Some((case, reason)) = counterexample {
+                failures.push(format!(
+                    "day{day:02}: {}: case {case}: {reason}",
+                    property.description
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}